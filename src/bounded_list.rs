@@ -0,0 +1,119 @@
+// A fourth::List that won't grow past a fixed length. Push one too many and the element at the far end gets
+// pushed out and handed to on_evict, which can log it, stash it somewhere, or just drop it. Keep pushing to the
+// front and you've got a history buffer: newest first, oldest falling off the back.
+//
+// Evictions always come from the opposite end to the push, so the element you just pushed is never the one that
+// goes (unless the limit is 0, when nothing can stay). That means push_back evicts from the front, not the back.
+// Always evicting from the back sounds simpler, but after a push_back the back is the element we just took, so a
+// full list would throw away everything new and keep the old. Use push_front if you want things to fall off the back.
+
+use crate::fourth::List;
+
+pub struct BoundedList<T, F> {
+  list: List<T>,
+  limit: usize,
+  on_evict: F,
+}
+
+impl<T, F: FnMut(T)> BoundedList<T, F> {
+  pub fn new(limit: usize, on_evict: F) -> Self {
+    BoundedList { list: List::new(), limit, on_evict }
+  }
+
+  pub fn limit(&self) -> usize {
+    self.limit
+  }
+
+  // Shrinking evicts from the back until everything fits again.
+  pub fn set_limit(&mut self, limit: usize) {
+    self.limit = limit;
+    while self.list.len() > self.limit {
+      let elem = self.list.pop_back().unwrap();
+      (self.on_evict)(elem);
+    }
+  }
+
+  // reading is free, writing has to go through us so the limit holds
+  pub fn list(&self) -> &List<T> {
+    &self.list
+  }
+
+  pub fn into_inner(self) -> List<T> {
+    self.list
+  }
+
+  pub fn len(&self) -> usize {
+    self.list.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.list.is_empty()
+  }
+
+  pub fn push_front(&mut self, elem: T) {
+    self.list.push_front(elem);
+    if self.list.len() > self.limit {
+      let elem = self.list.pop_back().unwrap();
+      (self.on_evict)(elem);
+    }
+  }
+
+  pub fn push_back(&mut self, elem: T) {
+    self.list.push_back(elem);
+    if self.list.len() > self.limit {
+      let elem = self.list.pop_front().unwrap();
+      (self.on_evict)(elem);
+    }
+  }
+
+  pub fn pop_front(&mut self) -> Option<T> {
+    self.list.pop_front()
+  }
+
+  pub fn pop_back(&mut self) -> Option<T> {
+    self.list.pop_back()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::BoundedList;
+
+  #[test]
+  fn history() {
+    let mut evicted = Vec::new();
+    let mut history = BoundedList::new(3, |cmd| evicted.push(cmd));
+    assert!(history.is_empty());
+
+    for cmd in &["ls", "cd src", "cargo build", "cargo test"] {
+      history.push_front(*cmd);
+    }
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.list().to_string(), "cargo test cargo build cd src");
+
+    // the other end works the other way round
+    history.push_back("vim");
+    assert_eq!(history.list().separated_by(", ").to_string(), "cargo build, cd src, vim");
+
+    // popping makes room without evicting anything
+    assert_eq!(history.pop_back(), Some("vim"));
+    history.push_front("git status");
+    assert_eq!(history.pop_front(), Some("git status"));
+
+    history.set_limit(1);
+    assert_eq!(history.limit(), 1);
+    assert_eq!(history.into_inner().into_vec(), vec!["cargo build"]);
+    assert_eq!(evicted, vec!["ls", "cargo test", "cd src"]);
+  }
+
+  #[test]
+  fn zero_limit() {
+    let mut count = 0;
+    let mut list = BoundedList::new(0, |_| count += 1);
+    list.push_front(1);
+    list.push_back(2);
+    assert!(list.is_empty());
+    drop(list);
+    assert_eq!(count, 2);
+  }
+}
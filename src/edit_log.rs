@@ -0,0 +1,179 @@
+// An undo/redo journal for the finger list. Every edit that goes through EditLog gets recorded, and undo plays the
+// inverse back. The neat part is that the inverse of an edit is itself an edit: undoing a push_left is a pop_left,
+// and undoing that pop_left is the original push_left again. So the redo stack is just the log of what undo did,
+// and redo is "undo the undo".
+//
+// Pops hang on to the element they took out, that's the only data we need: pushed elements are still in the list
+// when we come to undo them.
+
+use crate::silly1::{List, Stack};
+
+enum Edit<T> {
+  PushLeft,
+  PushRight,
+  PopLeft(T),
+  PopRight(T),
+  GoLeft,
+  GoRight,
+}
+
+pub struct EditLog<T> {
+  list: List<T>,
+  undo: Stack<Edit<T>>,
+  redo: Stack<Edit<T>>,
+}
+
+impl<T> EditLog<T> {
+  pub fn new(list: List<T>) -> Self {
+    EditLog { list, undo: Stack::new(), redo: Stack::new() }
+  }
+
+  // reading is free, writing has to go through the log
+  pub fn list(&self) -> &List<T> {
+    &self.list
+  }
+
+  pub fn into_inner(self) -> List<T> {
+    self.list
+  }
+
+  pub fn push_left(&mut self, elem: T) {
+    self.list.push_left(elem);
+    self.record(Edit::PushLeft);
+  }
+
+  pub fn push_right(&mut self, elem: T) {
+    self.list.push_right(elem);
+    self.record(Edit::PushRight);
+  }
+
+  // popped elements are moved into the log, so we hand back a clone
+  pub fn pop_left(&mut self) -> Option<T> where T: Clone {
+    let elem = self.list.pop_left()?;
+    self.record(Edit::PopLeft(elem.clone()));
+    Some(elem)
+  }
+
+  pub fn pop_right(&mut self) -> Option<T> where T: Clone {
+    let elem = self.list.pop_right()?;
+    self.record(Edit::PopRight(elem.clone()));
+    Some(elem)
+  }
+
+  pub fn go_left(&mut self) -> bool {
+    let moved = self.list.go_left();
+    if moved {
+      self.record(Edit::GoLeft);
+    }
+    moved
+  }
+
+  pub fn go_right(&mut self) -> bool {
+    let moved = self.list.go_right();
+    if moved {
+      self.record(Edit::GoRight);
+    }
+    moved
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undo.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redo.is_empty()
+  }
+
+  pub fn undo(&mut self) -> bool {
+    match self.undo.pop() {
+      Some(edit) => {
+        let inverse = self.invert(edit);
+        self.redo.push(inverse);
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn redo(&mut self) -> bool {
+    match self.redo.pop() {
+      Some(edit) => {
+        let inverse = self.invert(edit);
+        self.undo.push(inverse);
+        true
+      }
+      None => false,
+    }
+  }
+
+  // a fresh edit means the old future is gone
+  fn record(&mut self, edit: Edit<T>) {
+    self.undo.push(edit);
+    self.redo = Stack::new();
+  }
+
+  // do the opposite of edit to the list, and return the edit that would put it back
+  fn invert(&mut self, edit: Edit<T>) -> Edit<T> {
+    match edit {
+      Edit::PushLeft => Edit::PopLeft(self.list.pop_left().unwrap()),
+      Edit::PushRight => Edit::PopRight(self.list.pop_right().unwrap()),
+      Edit::PopLeft(elem) => {
+        self.list.push_left(elem);
+        Edit::PushLeft
+      }
+      Edit::PopRight(elem) => {
+        self.list.push_right(elem);
+        Edit::PushRight
+      }
+      Edit::GoLeft => {
+        self.list.go_right();
+        Edit::GoRight
+      }
+      Edit::GoRight => {
+        self.list.go_left();
+        Edit::GoLeft
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::EditLog;
+  use crate::silly1::List;
+
+  #[test]
+  fn undo_redo() {
+    let mut log = EditLog::new(List::from(vec![1, 2]));     // [_, 1, 2]
+    assert!(!log.undo());
+
+    log.push_left(0);                                       // [0, _, 1, 2]
+    assert!(log.go_right());                                // [0, 1, _, 2]
+    assert_eq!(log.pop_right(), Some(2));                   // [0, 1, _]
+    assert!(!log.go_right());
+    log.push_right(3);                                      // [0, 1, _, 3]
+    assert_eq!(format!("{:?}", log.list()), "[0, 1, _, 3]");
+
+    assert!(log.undo());
+    assert_eq!(format!("{:?}", log.list()), "[0, 1, _]");
+    assert!(log.undo());
+    assert_eq!(format!("{:?}", log.list()), "[0, 1, _, 2]");
+    assert!(log.undo());
+    assert_eq!(format!("{:?}", log.list()), "[0, _, 1, 2]");
+    assert!(log.undo());
+    assert_eq!(format!("{:?}", log.list()), "[_, 1, 2]");
+    assert!(!log.undo());
+
+    assert!(log.redo());
+    assert!(log.redo());
+    assert_eq!(format!("{:?}", log.list()), "[0, 1, _, 2]");
+
+    // a new edit throws away what was left to redo
+    assert_eq!(log.pop_left(), Some(1));                    // [0, _, 2]
+    assert!(!log.can_redo());
+    assert!(!log.redo());
+
+    assert!(log.undo());
+    assert_eq!(log.into_inner().into_vec(), vec![0, 1, 2]);
+  }
+}
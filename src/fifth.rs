@@ -35,14 +35,31 @@
 // One key observation is that we're wasting a ton of work doing the same thing over and over. Can we memoize this
 // work? Why, yes! We can store a pointer to the end of the list, and just jump straight to there!
 
-use std::ptr;
+// That first try kept the nodes in Boxes and the tail as a raw pointer into the last one. It works fine, right up
+// until you run it under Miri: every time head's Box gets touched (moved, or even just reborrowed by peek_mut), Stacked
+// Borrows treats it as a claim to unique access, and that quietly invalidates our raw tail pointer. The next push then
+// writes through a pointer that isn't allowed to exist any more. Mixing Box with raw pointers into the same memory is
+// asking for exactly this.
+//
+// So now it's raw pointers all the way down. Nodes get Box::into_raw'd the moment they're made, and only become Boxes
+// again in pop, right as they leave the list. Everything in between goes through NonNull, which is just a *mut that
+// promises not to be null, so Option<NonNull> is the same size as a bare pointer.
+//
+// NonNull<T> is covariant in T, same as Box, which is what we want: a List<&'static str> should be usable as a
+// List<&'a str>. It doesn't tell the compiler that we own any Ts though, so the PhantomData is there to say we do,
+// which drop check cares about.
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
 pub struct List<T> {
     head: Link<T>,
-    tail: *mut Node<T>,
+    tail: Link<T>,
+    len: usize,
+    _boo: PhantomData<T>,
 }
 
-type Link<T> = Option<Box<Node<T>>>;
+type Link<T> = Option<NonNull<Node<T>>>;
 
 struct Node<T> {
     elem: T,
@@ -51,82 +68,119 @@ struct Node<T> {
 
 impl<T> List<T> {
     pub fn new() -> Self {
-        List { head: None, tail: ptr::null_mut() }
+        List { head: None, tail: None, len: 0, _boo: PhantomData }
     }
 
     pub fn push(&mut self, elem: T) {
-        let mut new_tail = Box::new(Node {
-            elem: elem,
-            next: None,
-        });
-
-        let raw_tail: *mut _ = &mut *new_tail;
-
-        if !self.tail.is_null() {
-            unsafe {
-                (*self.tail).next = Some(new_tail);
+        // SAFETY: Box::into_raw never hands back null, and the old tail (if any) is a live node we own.
+        unsafe {
+            let new_tail = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+                elem,
+                next: None,
+            })));
+
+            if let Some(old_tail) = self.tail {
+                (*old_tail.as_ptr()).next = Some(new_tail);
+            } else {
+                self.head = Some(new_tail);
             }
-        } else {
-            self.head = Some(new_tail);
-        }
 
-        self.tail = raw_tail;
+            self.tail = Some(new_tail);
+        }
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        self.head.take().map(|head| {
-            let head = *head;
-            self.head = head.next;
+        // SAFETY: head came from Box::into_raw in push, and this is the only place it goes back into a Box.
+        self.head.map(|head| unsafe {
+            let boxed = Box::from_raw(head.as_ptr());
+            self.head = boxed.next;
 
             if self.head.is_none() {
-                self.tail = ptr::null_mut();
+                self.tail = None;
             }
+            self.len -= 1;
 
-            head.elem
+            boxed.elem
         })
     }
 
+    // Kept up to date by push and pop, so nobody has to walk the queue to find out how backed up it is.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn peek(&self) -> Option<&T> {
-        self.head.as_ref().map(|node| {
-            &node.elem
+        // SAFETY: the node lives as long as it's in the list, and &self keeps it there.
+        self.head.map(|node| unsafe {
+            &(*node.as_ptr()).elem
         })
     }
 
     pub fn peek_mut(&mut self) -> Option<&mut T> {
-        self.head.as_mut().map(|node| {
-            &mut node.elem
+        // SAFETY: as above, and &mut self means nobody else is looking.
+        self.head.map(|node| unsafe {
+            &mut (*node.as_ptr()).elem
         })
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
-    }
-
     pub fn iter(&self) -> Iter<'_, T> {
-        Iter { next: self.head.as_deref() }
+        Iter { next: self.head, _boo: PhantomData }
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        IterMut { next: self.head.as_deref_mut() }
+        IterMut { next: self.head, _boo: PhantomData }
     }
 }
 
 pub struct IntoIter<T>(List<T>);
 
+// The iterators hold raw pointers too, so the PhantomData is what ties them to the borrow of the list.
 pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+    next: Link<T>,
+    _boo: PhantomData<&'a T>,
 }
 
 pub struct IterMut<'a, T> {
-    next: Option<&'a mut Node<T>>,
+    next: Link<T>,
+    _boo: PhantomData<&'a mut T>,
 }
 
+// Raw pointers opt us out of Send and Sync, but the list owns its nodes outright just like a Box chain would, so it can
+// go wherever a T could. Same rules as std's LinkedList: a &List only hands out &Ts, so sharing it needs T: Sync, and
+// IterMut hands out &mut Ts, so sending one needs T: Send.
+unsafe impl<T: Send> Send for List<T> {}
+unsafe impl<T: Sync> Sync for List<T> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// pop turns each node back into a Box and frees it, one at a time, so there's no recursion to blow the stack.
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        let mut cur_link = self.head.take();
-        while let Some(mut boxed_node) = cur_link {
-            cur_link = boxed_node.next.take();
-        }
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
     }
 }
 
@@ -141,9 +195,10 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| {
-            self.next = node.next.as_deref();
-            &node.elem
+        // SAFETY: every node we can reach is owned by the list, which is borrowed for 'a.
+        self.next.map(|node| unsafe {
+            self.next = (*node.as_ptr()).next;
+            &(*node.as_ptr()).elem
         })
     }
 }
@@ -152,9 +207,10 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.take().map(|node| {
-            self.next = node.next.as_deref_mut();
-            &mut node.elem
+        // SAFETY: as for Iter, and we step past each node before handing it out, so no element is given out twice.
+        self.next.map(|node| unsafe {
+            self.next = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).elem
         })
     }
 }
@@ -200,6 +256,59 @@ mod test {
         assert_eq!(list.pop(), None);
     }
 
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+
+        list.pop();
+        assert_eq!(list.len(), 2);
+        list.pop(); list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        // popping an empty queue leaves it at zero
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.len(), 0);
+        list.push(4);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+        list.push(1); list.push(2); list.push(3);
+
+        // it's a queue, so the head is the oldest
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(list.peek_mut(), Some(&mut 1));
+
+        if let Some(value) = list.peek_mut() {
+            *value = 42
+        }
+
+        assert_eq!(list.peek(), Some(&42));
+        assert_eq!(list.pop(), Some(42));
+        assert_eq!(list.peek(), Some(&2));
+
+        // and through the last node, where tail points too
+        list.pop();
+        if let Some(value) = list.peek_mut() {
+            *value = 7
+        }
+        list.push(4);
+        assert_eq!(list.pop(), Some(7));
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.peek(), None);
+    }
+
     #[test]
     fn into_iter() {
         let mut list = List::new();
@@ -212,6 +321,22 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn into_iter_for_loop() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut seen = Vec::new();
+        for elem in list {
+            seen.push(elem);
+        }
+        assert_eq!(seen, vec![1, 2, 3]);
+
+        let mut list = List::new();
+        list.push(4); list.push(5);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![4, 5]);
+    }
+
     #[test]
     fn iter() {
         let mut list = List::new();
@@ -224,6 +349,32 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn mixed_access() {
+        // the sequence that upset Miri before: take a &mut into the head, then push through tail, then read it all back
+        let mut list = List::new();
+        list.push(1); list.push(2);
+
+        *list.peek_mut().unwrap() *= 10;
+        list.push(3);
+        for elem in list.iter_mut() {
+            *elem += 1;
+        }
+        list.push(4);
+        assert_eq!(list.peek(), Some(&11));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&11, &3, &4, &4]);
+
+        assert_eq!(list.pop(), Some(11));
+        *list.peek_mut().unwrap() = 0;
+        list.push(5);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![0, 4, 4, 5]);
+    }
+
+    #[allow(dead_code)]
+    fn covariant<'a>(list: List<&'static str>) -> List<&'a str> {
+        list
+    }
+
     #[test]
     fn iter_mut() {
         let mut list = List::new();
@@ -235,4 +386,56 @@ mod test {
         assert_eq!(iter.next(), Some(&mut 3));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn long_drop() {
+        // a recursive drop of the node chain would blow the stack long before this. Miri would take all day over
+        // that many nodes though, and a few thousand is plenty for it to check the frees
+        let len = if cfg!(miri) { 2_000 } else { 200_000 };
+        let mut list = List::new();
+        for i in 0..len {
+            list.push(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn drop_frees_every_elem() {
+        // with raw pointers nothing frees a node unless we do, so make sure they all go
+        let shared = std::rc::Rc::new(());
+        let mut list = List::new();
+        for _ in 0..10 {
+            list.push(shared.clone());
+        }
+        list.pop();
+        assert_eq!(std::rc::Rc::strong_count(&shared), 10);
+        drop(list);
+        assert_eq!(std::rc::Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<List<i32>>();
+        is_send_sync::<super::IntoIter<i32>>();
+        is_send_sync::<super::Iter<'_, i32>>();
+        is_send_sync::<super::IterMut<'_, i32>>();
+    }
+
+    #[test]
+    fn worker_thread() {
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+
+        let queue = Arc::new(Mutex::new(List::new()));
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || for i in 0..1000 { queue.lock().unwrap().push(i) })
+        };
+        producer.join().unwrap();
+
+        let queue = Arc::try_unwrap(queue).ok().unwrap().into_inner().unwrap();
+        assert_eq!(queue.len(), 1000);
+        assert!(queue.into_iter().eq(0..1000));
+    }
 }
@@ -22,7 +22,7 @@ impl List {
 
   pub fn push(&mut self, elem: i32) {
     let new_node = Box::new(Node {
-        elem: elem,
+        elem,
         next: mem::replace(&mut self.head, Link::Empty),
     });
 
@@ -40,6 +40,12 @@ impl List {
   }
 }
 
+impl Default for List {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Drop for List {
   fn drop(&mut self) {
     let mut cur_link = mem::replace(&mut self.head, Link::Empty);
@@ -14,43 +14,453 @@
 //
 // Alright, we want to be doubly-linked. This means each node has a pointer to the previous and next node. Also, the list itself has a pointer to the first and last node. This gives us fast insertion and removal on both ends of the list.
 
-use std::rc::Rc;
-use std::cell::{Ref, RefCell, RefMut};
+use std::collections::{LinkedList, VecDeque};
+use std::fmt;
+use std::marker::PhantomData;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::rc::{self, Rc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{self, Arc, RwLock};
+use std::cell::{BorrowError, BorrowMutError, Ref, RefMut};
 
-pub struct List<T> {
-  head: Link<T>,
-  tail: Link<T>,
+use cell::NodeCell;
+
+// RefCell is only one way to get at a node through a shared pointer, and nothing below really cares which one it
+// is, as long as there's a way to borrow it and a way to borrow it mutably. So the list takes a Kind that picks
+// the cell, along with the pointer that goes round it:
+//
+// - Checked is what we've had all along: Rc<RefCell>, and a clash panics at runtime.
+// - Locked is Arc<RwLock>, so the whole list can go to another thread, or be read from several at once. It's a
+//   RwLock rather than a Mutex because our own shared borrows overlap (peeking one end while walking, comparing a
+//   list with itself), and with a Mutex those would deadlock.
+//
+// What there isn't is a kind that skips the checks. It's tempting, since the borrow checker keeps any one list's
+// borrows from clashing, but a handle can point into some other list whose nodes we've got no borrow on at all.
+// Get one thing wrong there and it's undefined behaviour rather than a panic.
+//
+// The list trusts upgrade, ptr_eq and friends to behave (node_at, swap and detach all do), and get and find lean
+// on the node staying put to hand out borrows that outlive the guard they came through. So these two are the only
+// kinds there are, and the trait's sealed to keep it that way.
+pub trait Kind: Sized + sealed::Sealed {
+  type Cell<U>: NodeCell<U>;
+  type Shared<U>: Clone + Deref<Target = Self::Cell<U>>;
+  type Weak<U>: Clone;
+  // What peek, get, find and CursorMut::current hand out. Checked's are plain std Ref and RefMut, same as before
+  // there were kinds. Locked can't map its guards down to the element, so it gets ElemRef/ElemRefMut.
+  type Ref<'a, T: 'a>: Deref<Target = T>;
+  type RefMut<'a, T: 'a>: DerefMut<Target = T>;
+
+  fn new<U>(value: U) -> Self::Shared<U>;
+  fn downgrade<U>(this: &Self::Shared<U>) -> Self::Weak<U>;
+  fn upgrade<U>(this: &Self::Weak<U>) -> Option<Self::Shared<U>>;
+  fn ptr_eq<U>(a: &Self::Shared<U>, b: &Self::Shared<U>) -> bool;
+  // Only works for the last strong pointer, like Rc::try_unwrap, and takes the value out of the cell too.
+  fn into_inner<U>(this: Self::Shared<U>) -> Result<U, Self::Shared<U>>;
+  fn elem<'a, T>(node: NodeRef<'a, T, Self>) -> Self::Ref<'a, T>;
+  fn elem_mut<'a, T>(node: NodeRefMut<'a, T, Self>) -> Self::RefMut<'a, T>;
+}
+
+mod sealed {
+  pub trait Sealed {}
+
+  impl Sealed for super::Checked {}
+  impl Sealed for super::Locked {}
+}
+
+pub struct Checked;
+pub struct Locked;
+
+// With the borrow-audit feature on, Checked's RefCell gets wrapped to remember who borrowed each node last, so
+// a clash can name names instead of just saying "already borrowed".
+#[cfg(not(feature = "borrow-audit"))]
+type CheckedCell<U> = std::cell::RefCell<U>;
+#[cfg(feature = "borrow-audit")]
+type CheckedCell<U> = cell::AuditCell<U>;
+
+impl Kind for Checked {
+  type Cell<U> = CheckedCell<U>;
+  type Shared<U> = Rc<CheckedCell<U>>;
+  type Weak<U> = rc::Weak<CheckedCell<U>>;
+  type Ref<'a, T: 'a> = Ref<'a, T>;
+  type RefMut<'a, T: 'a> = RefMut<'a, T>;
+
+  fn new<U>(value: U) -> Self::Shared<U> {
+    Rc::new(CheckedCell::new(value))
+  }
+
+  fn downgrade<U>(this: &Self::Shared<U>) -> Self::Weak<U> {
+    Rc::downgrade(this)
+  }
+
+  fn upgrade<U>(this: &Self::Weak<U>) -> Option<Self::Shared<U>> {
+    this.upgrade()
+  }
+
+  fn ptr_eq<U>(a: &Self::Shared<U>, b: &Self::Shared<U>) -> bool {
+    Rc::ptr_eq(a, b)
+  }
+
+  fn into_inner<U>(this: Self::Shared<U>) -> Result<U, Self::Shared<U>> {
+    Rc::try_unwrap(this).map(CheckedCell::into_inner)
+  }
+
+  fn elem<'a, T>(node: NodeRef<'a, T, Self>) -> Ref<'a, T> {
+    Ref::map(node, |node| &node.elem)
+  }
+
+  fn elem_mut<'a, T>(node: NodeRefMut<'a, T, Self>) -> RefMut<'a, T> {
+    RefMut::map(node, |node| &mut node.elem)
+  }
+}
+
+impl Kind for Locked {
+  type Cell<U> = RwLock<U>;
+  type Shared<U> = Arc<RwLock<U>>;
+  type Weak<U> = sync::Weak<RwLock<U>>;
+  type Ref<'a, T: 'a> = ElemRef<'a, T, Self>;
+  type RefMut<'a, T: 'a> = ElemRefMut<'a, T, Self>;
+
+  fn new<U>(value: U) -> Self::Shared<U> {
+    Arc::new(RwLock::new(value))
+  }
+
+  fn downgrade<U>(this: &Self::Shared<U>) -> Self::Weak<U> {
+    Arc::downgrade(this)
+  }
+
+  fn upgrade<U>(this: &Self::Weak<U>) -> Option<Self::Shared<U>> {
+    this.upgrade()
+  }
+
+  fn ptr_eq<U>(a: &Self::Shared<U>, b: &Self::Shared<U>) -> bool {
+    Arc::ptr_eq(a, b)
+  }
+
+  fn into_inner<U>(this: Self::Shared<U>) -> Result<U, Self::Shared<U>> {
+    Arc::try_unwrap(this).map(|lock| lock.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()))
+  }
+
+  fn elem<'a, T>(node: NodeRef<'a, T, Self>) -> Self::Ref<'a, T> {
+    ElemRef(node)
+  }
+
+  fn elem_mut<'a, T>(node: NodeRefMut<'a, T, Self>) -> Self::RefMut<'a, T> {
+    ElemRefMut(node)
+  }
+}
+
+// The borrowing half of a Kind. It lives in here so the guards' types don't clutter up the rest of the file.
+mod cell {
+  use std::cell::RefCell;
+  use std::ops::{Deref, DerefMut};
+  use std::sync::RwLock;
+
+  pub trait NodeCell<U> {
+    type Ref<'a>: Deref<Target = U> where Self: 'a;
+    type RefMut<'a>: DerefMut<Target = U> where Self: 'a;
+
+    fn borrow(&self) -> Self::Ref<'_>;
+    fn borrow_mut(&self) -> Self::RefMut<'_>;
+  }
+
+  impl<U> NodeCell<U> for RefCell<U> {
+    type Ref<'a> = std::cell::Ref<'a, U> where Self: 'a;
+    type RefMut<'a> = std::cell::RefMut<'a, U> where Self: 'a;
+
+    fn borrow(&self) -> Self::Ref<'_> {
+      RefCell::borrow(self)
+    }
+
+    fn borrow_mut(&self) -> Self::RefMut<'_> {
+      RefCell::borrow_mut(self)
+    }
+  }
+
+  // Nothing we do under a lock can panic halfway through relinking, so a poisoned one is still in one piece.
+  impl<U> NodeCell<U> for RwLock<U> {
+    type Ref<'a> = std::sync::RwLockReadGuard<'a, U> where Self: 'a;
+    type RefMut<'a> = std::sync::RwLockWriteGuard<'a, U> where Self: 'a;
+
+    fn borrow(&self) -> Self::Ref<'_> {
+      self.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn borrow_mut(&self) -> Self::RefMut<'_> {
+      self.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+  }
+
+  // A RefCell that notes down which List operation borrowed it last (see audit), and when a borrow clashes, panics
+  // with both the operation that wanted it and that name. It's only ever a name for the last borrow, not whoever's
+  // holding one now: the guards are plain std Ref and RefMut, so nothing tells us when they go. Usually that's the
+  // same thing, since the guard you forgot about is the one that gets in the way. But if something else borrows
+  // the node in between and lets go again, the blame lands on that instead.
+  #[cfg(feature = "borrow-audit")]
+  pub struct AuditCell<U> {
+    inner: RefCell<U>,
+    last: std::cell::Cell<Option<&'static str>>,
+  }
+
+  #[cfg(feature = "borrow-audit")]
+  impl<U> AuditCell<U> {
+    pub(super) fn new(value: U) -> Self {
+      AuditCell { inner: RefCell::new(value), last: std::cell::Cell::new(None) }
+    }
+
+    pub(super) fn into_inner(self) -> U {
+      self.inner.into_inner()
+    }
+
+    pub(super) fn borrow(&self) -> std::cell::Ref<'_, U> {
+      self.try_borrow().unwrap_or_else(|_| self.clash(""))
+    }
+
+    pub(super) fn borrow_mut(&self) -> std::cell::RefMut<'_, U> {
+      self.try_borrow_mut().unwrap_or_else(|_| self.clash(" mutably"))
+    }
+
+    pub(super) fn try_borrow(&self) -> Result<std::cell::Ref<'_, U>, std::cell::BorrowError> {
+      let inner = self.inner.try_borrow()?;
+      self.last.set(Some(super::audit::current()));
+      Ok(inner)
+    }
+
+    pub(super) fn try_borrow_mut(&self) -> Result<std::cell::RefMut<'_, U>, std::cell::BorrowMutError> {
+      let inner = self.inner.try_borrow_mut()?;
+      self.last.set(Some(super::audit::current()));
+      Ok(inner)
+    }
+
+    fn clash(&self, how: &str) -> ! {
+      panic!(
+        "{} couldn't borrow a node{}: {} borrowed it last",
+        super::audit::current(), how, self.last.get().unwrap_or("something outside the list"),
+      )
+    }
+  }
+
+  #[cfg(feature = "borrow-audit")]
+  impl<U> NodeCell<U> for AuditCell<U> {
+    type Ref<'a> = std::cell::Ref<'a, U> where Self: 'a;
+    type RefMut<'a> = std::cell::RefMut<'a, U> where Self: 'a;
+
+    fn borrow(&self) -> Self::Ref<'_> {
+      AuditCell::borrow(self)
+    }
+
+    fn borrow_mut(&self) -> Self::RefMut<'_> {
+      AuditCell::borrow_mut(self)
+    }
+  }
+}
+
+// Which List operation is running on this thread, for AuditCell to pin its borrows on. Every public method that
+// touches a node starts with audit::enter. Nested calls keep the outermost name, since that's the one the caller
+// actually made. Without the borrow-audit feature all of this compiles away to nothing.
+mod audit {
+  #[cfg(feature = "borrow-audit")]
+  use std::cell::Cell;
+
+  #[cfg(feature = "borrow-audit")]
+  thread_local! {
+    static CURRENT: Cell<Option<&'static str>> = const { Cell::new(None) };
+  }
+
+  pub struct Op {
+    #[cfg(feature = "borrow-audit")]
+    outermost: bool,
+  }
+
+  #[cfg(feature = "borrow-audit")]
+  pub fn enter(name: &'static str) -> Op {
+    let outermost = CURRENT.with(|current| current.get().is_none());
+    if outermost {
+      CURRENT.with(|current| current.set(Some(name)));
+    }
+    Op { outermost }
+  }
+
+  #[cfg(not(feature = "borrow-audit"))]
+  #[inline(always)]
+  pub fn enter(_name: &'static str) -> Op {
+    Op {}
+  }
+
+  #[cfg(feature = "borrow-audit")]
+  pub fn current() -> &'static str {
+    CURRENT.with(|current| current.get()).unwrap_or("something outside the list")
+  }
+
+  #[cfg(feature = "borrow-audit")]
+  impl Drop for Op {
+    fn drop(&mut self) {
+      if self.outermost {
+        CURRENT.with(|current| current.set(None));
+      }
+    }
+  }
+}
+
+pub struct List<T, K: Kind = Checked> {
+  head: Link<T, K>,
+  tail: Link<T, K>,
+  len: usize,
+  id: usize,
+}
+
+// Every list gets an id of its own, and every node carries the id of the list it's in. That's how a handle gets
+// checked against the list it's used on: looking at the node's neighbours can't tell us, since a node in the
+// middle of some other list has both. Ids only have to differ between lists alive at the same time, but a
+// counter that never repeats gets us that for free.
+//
+// The id sits in an Arc of its own rather than in the node, so a handle can check it without touching the node at
+// all (see resolve). Relaxed is plenty: moving a node between lists takes &mut on both, so whoever asks next has
+// already synced with whoever moved it.
+fn next_id() -> usize {
+  static NEXT: AtomicUsize = AtomicUsize::new(0);
+  NEXT.fetch_add(1, Ordering::Relaxed)
 }
 
-type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+type Shared<T, K> = <K as Kind>::Shared<Node<T, K>>;
+type Link<T, K> = Option<Shared<T, K>>;
+// prev only ever points back at a node that already owns us through its next, so it mustn't own anything
+// itself. If it did, every pair of neighbours would be a little Rc cycle that nothing could ever free.
+type WeakLink<T, K> = Option<<K as Kind>::Weak<Node<T, K>>>;
+type Owner = Arc<AtomicUsize>;
 
-struct Node<T> {
-  elem: T,
-  next: Link<T>,
-  prev: Link<T>,
+// Node gets a module to itself so Kind::elem can mention it without it becoming nameable from outside.
+mod node {
+  use super::{Kind, Link, Owner, WeakLink};
+
+  pub struct Node<T, K: Kind> {
+    pub(super) elem: T,
+    pub(super) next: Link<T, K>,
+    pub(super) prev: WeakLink<T, K>,
+    pub(super) owner: Owner,
+  }
 }
+use node::Node;
 
-impl<T> Node<T> {
-  fn new(elem: T) -> Rc<RefCell<Self>>{
-    Rc::new(RefCell::new(Node {
-      elem: elem,
+impl<T, K: Kind> Node<T, K> {
+  fn new(elem: T, owner: usize) -> Shared<T, K> {
+    K::new(Node {
+      elem,
       next: None,
       prev: None,
-    }))
+      owner: Arc::new(AtomicUsize::new(owner)),
+    })
+  }
+
+  // Hands every node from here to the end of the chain over to the list with id owner.
+  fn retag(mut cur: Link<T, K>, owner: usize) {
+    while let Some(node) = cur {
+      let node = node.borrow();
+      node.owner.store(owner, Ordering::Relaxed);
+      cur = node.next.clone();
+    }
+  }
+}
+
+type NodeRef<'a, T, K> = <<K as Kind>::Cell<Node<T, K>> as NodeCell<Node<T, K>>>::Ref<'a>;
+type NodeRefMut<'a, T, K> = <<K as Kind>::Cell<Node<T, K>> as NodeCell<Node<T, K>>>::RefMut<'a>;
+
+// What peek and friends hand out for Locked: a borrow of the whole node, showing only its element.
+// Checked gets to Ref::map its way down to the element instead, but not every cell's guard can be mapped.
+pub struct ElemRef<'a, T: 'a, K: Kind + 'a>(NodeRef<'a, T, K>);
+pub struct ElemRefMut<'a, T: 'a, K: Kind + 'a>(NodeRefMut<'a, T, K>);
+
+impl<'a, T, K: Kind> Deref for ElemRef<'a, T, K> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0.elem
+  }
+}
+
+impl<'a, T, K: Kind> Deref for ElemRefMut<'a, T, K> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0.elem
+  }
+}
+
+impl<'a, T, K: Kind> DerefMut for ElemRefMut<'a, T, K> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.0.elem
+  }
+}
+
+// A weak pointer to one node, as handed back by push_front and push_back. It doesn't keep the node alive, so
+// once the element's been taken out it just stops working. It does stay tied to the node rather than the list,
+// though: if the node gets moved into another list (append, split_off, ...) the handle goes with it, and using it
+// on the old list panics.
+//
+// Alongside the node it keeps a weak pointer to the node's owner id. Only the node holds that one strongly, so the
+// id goes when the node does.
+pub struct NodeHandle<T, K: Kind = Checked>(K::Weak<Node<T, K>>, sync::Weak<AtomicUsize>);
+
+impl<T, K: Kind> NodeHandle<T, K> {
+  fn new(node: &Shared<T, K>) -> Self {
+    NodeHandle(K::downgrade(node), Arc::downgrade(&node.borrow().owner))
   }
 }
 
+impl<T, K: Kind> Clone for NodeHandle<T, K> {
+  fn clone(&self) -> Self {
+    NodeHandle(self.0.clone(), self.1.clone())
+  }
+}
+
+// Lets a borrow of a node outlive the guard we reached it through. SAFETY: callers promise the node's owned by a
+// list that stays borrowed for 'a, so it can't be unlinked and freed in the meantime.
+unsafe fn unbind<'a, U>(node: &U) -> &'a U {
+  &*(node as *const U)
+}
+
 impl<T> List<T> {
   pub fn new() -> Self {
-    List { head:None, tail: None}
+    List::with_cell()
+  }
+}
+
+impl<T, K: Kind> List<T, K> {
+  pub fn with_cell() -> Self {
+    List { head: None, tail: None, len: 0, id: next_id() }
   }
 
-  pub fn push_front(&mut self, elem: T) {
-    let new_head = Node::new(elem);
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  // Each push hands back a handle to the node it made, which remove_node and move_to_front/back can use to find it
+  // again without a walk. Ignore it if you don't need it.
+  pub fn push_front(&mut self, elem: T) -> NodeHandle<T, K> {
+    let _op = audit::enter("List::push_front");
+    let node = Node::<T, K>::new(elem, self.id);
+    let handle = NodeHandle::new(&node);
+    self.push_front_node(node);
+    handle
+  }
+
+  pub fn push_back(&mut self, elem: T) -> NodeHandle<T, K> {
+    let _op = audit::enter("List::push_back");
+    let node = Node::<T, K>::new(elem, self.id);
+    let handle = NodeHandle::new(&node);
+    self.push_back_node(node);
+    handle
+  }
 
+  fn push_front_node(&mut self, new_head: Shared<T, K>) {
+    self.len += 1;
     match self.head.take() {
       Some(old_head) => {
-        old_head.borrow_mut().prev = Some(new_head.clone());
+        old_head.borrow_mut().prev = Some(K::downgrade(&new_head));
         new_head.borrow_mut().next = Some(old_head);
         self.head = Some(new_head);
       }
@@ -61,12 +471,12 @@ impl<T> List<T> {
     }
   }
 
-  pub fn push_back(&mut self, elem: T) {
-    let new_tail = Node::new(elem);
+  fn push_back_node(&mut self, new_tail: Shared<T, K>) {
+    self.len += 1;
     match self.tail.take() {
       Some(old_tail) => {
         old_tail.borrow_mut().next = Some(new_tail.clone());
-        new_tail.borrow_mut().prev = Some(old_tail);
+        new_tail.borrow_mut().prev = Some(K::downgrade(&old_tail));
         self.tail = Some(new_tail);
       }
       None => {
@@ -77,7 +487,9 @@ impl<T> List<T> {
   }
 
   pub fn pop_front(&mut self) -> Option<T> {
+    let _op = audit::enter("List::pop_front");
     self.head.take().map(|old_head| {
+      self.len -= 1;
       match old_head.borrow_mut().next.take() {
         Some(new_head) => {
           new_head.borrow_mut().prev.take();
@@ -87,13 +499,16 @@ impl<T> List<T> {
           self.tail.take();
         }
       }
-      Rc::try_unwrap(old_head).ok().unwrap().into_inner().elem
+      K::into_inner(old_head).ok().unwrap().elem
     })
   }
 
   pub fn pop_back(&mut self) -> Option<T> {
+    let _op = audit::enter("List::pop_back");
     self.tail.take().map(|old_tail| {
-      match old_tail.borrow_mut().prev.take() {
+      self.len -= 1;
+      // the node before us still holds us through its next, so it can't be gone while we're the tail
+      match old_tail.borrow_mut().prev.take().and_then(|prev| K::upgrade(&prev)) {
         Some(new_tail) => {
           new_tail.borrow_mut().next.take();
           self.tail = Some(new_tail)
@@ -102,139 +517,2200 @@ impl<T> List<T> {
           self.head.take();
         }
       }
-      Rc::try_unwrap(old_tail).ok().unwrap().into_inner().elem
+      K::into_inner(old_tail).ok().unwrap().elem
     })
   }
 
-  pub fn peek_front(&self) -> Option<Ref<T>>{
-    self.head.as_ref().map(|node| {
-      Ref::map(node.borrow(), |node| &node.elem)
-    })
+  pub fn peek_front(&self) -> Option<K::Ref<'_, T>>{
+    let _op = audit::enter("List::peek_front");
+    self.head.as_ref().map(|node| K::elem(node.borrow()))
   }
 
-  pub fn peek_back(&self) -> Option<Ref<T>> {
-    self.tail.as_ref().map(|node| {
-      Ref::map(node.borrow(), |node| &node.elem)
-    })
+  pub fn peek_back(&self) -> Option<K::Ref<'_, T>> {
+    let _op = audit::enter("List::peek_back");
+    self.tail.as_ref().map(|node| K::elem(node.borrow()))
   }
 
-  pub fn peek_front_mut(&mut self) -> Option<RefMut<T>> {
-    self.head.as_ref().map(|node| {
-      RefMut::map(node.borrow_mut(), |node| &mut node.elem)
-    })
+  pub fn peek_front_mut(&mut self) -> Option<K::RefMut<'_, T>> {
+    let _op = audit::enter("List::peek_front_mut");
+    self.head.as_ref().map(|node| K::elem_mut(node.borrow_mut()))
   }
 
-  pub fn peek_back_mut(&mut self) -> Option<RefMut<T>> {
-    self.tail.as_ref().map(|node| {
-      RefMut::map(node.borrow_mut(), |node| &mut node.elem)
-    })
+  pub fn peek_back_mut(&mut self) -> Option<K::RefMut<'_, T>> {
+    let _op = audit::enter("List::peek_back_mut");
+    self.tail.as_ref().map(|node| K::elem_mut(node.borrow_mut()))
   }
 
-  pub fn into_iter(self) -> IntoIter<T> {
-    IntoIter(self)
+  // Hooks all of other's nodes onto our back. Only the two nodes at the seam get relinked, however long either list
+  // is, though the shorter of the two does get walked to retag its nodes (see adopt).
+  pub fn append(&mut self, other: &mut Self) {
+    let _op = audit::enter("List::append");
+    self.adopt(other);
+    let (other_head, other_tail) = match (other.head.take(), other.tail.take()) {
+      (Some(head), Some(tail)) => (head, tail),
+      _ => return,
+    };
+    match self.tail.take() {
+      Some(old_tail) => {
+        other_head.borrow_mut().prev = Some(K::downgrade(&old_tail));
+        old_tail.borrow_mut().next = Some(other_head);
+      }
+      None => self.head = Some(other_head),
+    }
+    self.tail = Some(other_tail);
+    self.len += std::mem::replace(&mut other.len, 0);
   }
-}
 
-impl<T> Drop for List<T>{
-  fn drop(&mut self) {
-    while self.pop_front().is_some() {}
+  // Same as append, just with other's nodes going in front of ours.
+  pub fn prepend(&mut self, other: &mut Self) {
+    let _op = audit::enter("List::prepend");
+    std::mem::swap(self, other);
+    self.append(other);
   }
-}
 
-pub struct IntoIter<T>(List<T>);
+  // Everything from at onwards moves into the returned list, nodes and all.
+  pub fn split_off(&mut self, at: usize) -> Self {
+    let _op = audit::enter("List::split_off");
+    assert!(at <= self.len, "split index {} out of bounds for a list of length {}", at, self.len);
+    if at == 0 {
+      return std::mem::take(self);
+    }
+    if at == self.len {
+      return List::with_cell();
+    }
 
-impl<T> Iterator for IntoIter<T> {
-  type Item = T;
+    let new_head = self.node_at(at);
+    let new_tail = new_head.borrow_mut().prev.take().and_then(|prev| K::upgrade(&prev)).unwrap();
+    new_tail.borrow_mut().next.take();
 
-  fn next(&mut self) -> Option<T>{
-    self.0.pop_front()
+    let tail = self.tail.replace(new_tail);
+    let len = self.len - at;
+    self.len = at;
+
+    // one half has to move to a fresh id, and retagging the shorter one keeps the walk short
+    let id = next_id();
+    let id = if at < len {
+      Node::<T, K>::retag(self.head.clone(), id);
+      std::mem::replace(&mut self.id, id)
+    } else {
+      Node::<T, K>::retag(Some(new_head.clone()), id);
+      id
+    };
+    List { head: Some(new_head), tail, len, id }
   }
-}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
-  fn next_back(&mut self) -> Option<T>{
-    self.0.pop_back()
+  // Tags other's nodes as ours, ahead of them being linked in. Whichever list is shorter gets retagged, and when
+  // that's us we take on other's id instead, so hanging a few nodes off a long list (or vice versa) stays cheap.
+  fn adopt(&mut self, other: &mut Self) {
+    if self.len < other.len {
+      Node::<T, K>::retag(self.head.clone(), other.id);
+      std::mem::swap(&mut self.id, &mut other.id);
+    } else {
+      Node::<T, K>::retag(other.head.clone(), self.id);
+    }
   }
-}
 
-#[cfg(test)]
-mod test {
-  use super::List;
+  // Drops all of other in between index - 1 and index, so its front ends up at index. Only four links change,
+  // the two either side of the gap and other's two ends, though like append the shorter list gets retagged.
+  pub fn splice_at(&mut self, index: usize, mut other: Self) {
+    let _op = audit::enter("List::splice_at");
+    assert!(index <= self.len, "splice index {} out of bounds for a list of length {}", index, self.len);
+    if index == 0 {
+      self.prepend(&mut other);
+      return;
+    }
+    if index == self.len {
+      self.append(&mut other);
+      return;
+    }
+    self.adopt(&mut other);
+    let (other_head, other_tail) = match (other.head.take(), other.tail.take()) {
+      (Some(head), Some(tail)) => (head, tail),
+      _ => return,
+    };
 
-  #[test]
-  fn basics() {
-    let mut list = List::new();
-    // empty list behavior check
-    assert_eq!(list.pop_front(), None);
-    // populate list
-    list.push_front(1);
-    list.push_front(2);
-    list.push_front(3);
-    // check normal removal
-    assert_eq!(list.pop_front(), Some(3));
-    assert_eq!(list.pop_front(), Some(2));
-    // push more to list, check nothing is corrupted
-    list.push_front(4);
-    list.push_front(5);
-    // check normal removal
-    assert_eq!(list.pop_front(), Some(5));
-    assert_eq!(list.pop_front(), Some(4));
-    // check exhaustion
-    assert_eq!(list.pop_front(), Some(1));
-    assert_eq!(list.pop_front(), None);
+    let after = self.node_at(index);
+    let before = after.borrow().prev.as_ref().and_then(K::upgrade).unwrap();
+    after.borrow_mut().prev = Some(K::downgrade(&other_tail));
+    other_head.borrow_mut().prev = Some(K::downgrade(&before));
+    other_tail.borrow_mut().next = before.borrow_mut().next.replace(other_head);
+    self.len += std::mem::replace(&mut other.len, 0);
+  }
 
-    // ------ Back ---------
+  // There's no Index impl: it would have to hand out a bare &T, and a RefCell only gives those out inside a guard.
+  pub fn get(&self, index: usize) -> Option<K::Ref<'_, T>> {
+    let _op = audit::enter("List::get");
+    if index >= self.len {
+      return None;
+    }
+    // SAFETY: same as in find. The node is owned by the chain, and nothing can unlink it while we're borrowed.
+    let node = unsafe { unbind(&*self.node_at(index)) };
+    Some(K::elem(node.borrow()))
+  }
 
-    // check empty list behaves itself
-    assert_eq!(list.pop_back(), None);
+  pub fn get_mut(&mut self, index: usize) -> Option<K::RefMut<'_, T>> {
+    let _op = audit::enter("List::get_mut");
+    if index >= self.len {
+      return None;
+    }
+    // SAFETY: as above, and &mut self rules out any other guard into the list.
+    let node = unsafe { unbind(&*self.node_at(index)) };
+    Some(K::elem_mut(node.borrow_mut()))
+  }
 
-    // populate list
-    list.push_back(1);
-    list.push_back(2);
-    list.push_back(3);
+  // For when T is cheap to copy and a guard is more trouble than it's worth.
+  pub fn get_cloned(&self, index: usize) -> Option<T> where T: Clone {
+    let _op = audit::enter("List::get_cloned");
+    self.get(index).map(|elem| elem.clone())
+  }
 
-    // check normal removal
-    assert_eq!(list.pop_back(), Some(3));
-    assert_eq!(list.pop_back(), Some(2));
+  // Walks to the node at index, coming in from whichever end is nearer. We hold an Rc to each node as we go
+  // rather than a borrow, so there's no chain of Refs to keep alive. Callers check the index.
+  fn node_at(&self, index: usize) -> Shared<T, K> {
+    debug_assert!(index < self.len);
+    if index < self.len - index {
+      let mut cur = self.head.clone().unwrap();
+      for _ in 0..index {
+        let next = cur.borrow().next.clone().unwrap();
+        cur = next;
+      }
+      cur
+    } else {
+      let mut cur = self.tail.clone().unwrap();
+      for _ in index..self.len - 1 {
+        let prev = cur.borrow().prev.as_ref().and_then(K::upgrade).unwrap();
+        cur = prev;
+      }
+      cur
+    }
+  }
 
-    // Push some more just to make sure nothing's corrupted
-    list.push_back(4);
-    list.push_back(5);
+  // Puts elem at index, shuffling everything from there on back one. index == len is fine, that's push_back.
+  pub fn insert(&mut self, index: usize, elem: T) {
+    let _op = audit::enter("List::insert");
+    assert!(index <= self.len, "insertion index {} out of bounds for a list of length {}", index, self.len);
+    if index == self.len {
+      self.push_back(elem);
+    } else {
+      let node = self.node_at(index);
+      self.insert_before_node(&node, elem);
+    }
+  }
 
-    // check normal removal
-    assert_eq!(list.pop_back(), Some(5));
-    assert_eq!(list.pop_back(), Some(4));
+  pub fn remove(&mut self, index: usize) -> Option<T> {
+    let _op = audit::enter("List::remove");
+    if index >= self.len {
+      return None;
+    }
+    let node = self.node_at(index);
+    Some(self.unlink(node))
+  }
 
-    // check exhaustion
-    assert_eq!(list.pop_back(), Some(1));
-    assert_eq!(list.pop_back(), None);
+  // Keeps only the elements pred likes, in order. The ones that go are unlinked on the spot, and we've
+  // already grabbed the next node before doing it, so the walk carries on from the right place.
+  pub fn retain<F>(&mut self, mut pred: F) where F: FnMut(&T) -> bool {
+    let _op = audit::enter("List::retain");
+    let mut cur = self.head.clone();
+    while let Some(node) = cur {
+      let keep = pred(&node.borrow().elem);
+      cur = node.borrow().next.clone();
+      if !keep {
+        self.unlink(node);
+      }
+    }
   }
 
-  #[test]
-  fn peek() {
-    let mut list = List::new();
-    assert!(list.peek_front().is_none());
-    assert!(list.peek_back().is_none());
-    assert!(list.peek_front_mut().is_none());
-    assert!(list.peek_back_mut().is_none());
+  pub fn contains(&self, elem: &T) -> bool where T: PartialEq {
+    let _op = audit::enter("List::contains");
+    self.find(|x| x == elem).is_some()
+  }
 
-    list.push_front(1);
-    list.push_front(2);
-    list.push_front(3);
+  pub fn find<P>(&self, mut pred: P) -> Option<K::Ref<'_, T>> where P: FnMut(&T) -> bool {
+    let _op = audit::enter("List::find");
+    let mut cur = self.head.as_deref();
+    while let Some(node) = cur {
+      let borrowed = node.borrow();
+      if pred(&borrowed.elem) {
+        return Some(K::elem(borrowed));
+      }
+      // This is the bit RefCell can't see for itself: the next node lives as long as we do, not just as long as
+      // the borrow we reached it through. SAFETY: every node from head on is owned by the chain, and unlinking
+      // one takes &mut self, which nobody can have while we're borrowed.
+      cur = borrowed.next.as_ref().map(|next| unsafe { unbind(&**next) });
+    }
+    None
+  }
 
-    assert_eq!(&*list.peek_front().unwrap(), &3);
-    assert_eq!(&mut *list.peek_front_mut().unwrap(), &mut 3);
-    assert_eq!(&*list.peek_back().unwrap(), &1);
-    assert_eq!(&mut *list.peek_back_mut().unwrap(), &mut 1);
+  fn insert_before_node(&mut self, node: &Shared<T, K>, elem: T) {
+    let prev = node.borrow().prev.as_ref().and_then(K::upgrade);
+    match prev {
+      Some(prev) => {
+        let new = Node::<T, K>::new(elem, self.id);
+        new.borrow_mut().prev = Some(K::downgrade(&prev));
+        new.borrow_mut().next = Some(node.clone());
+        node.borrow_mut().prev = Some(K::downgrade(&new));
+        prev.borrow_mut().next = Some(new);
+        self.len += 1;
+      }
+      None => {
+        self.push_front(elem);
+      }
+    }
   }
 
-  #[test]
-  fn into_iter() {
-    let mut list = List::new();
+  pub fn dedup(&mut self) where T: PartialEq {
+    let _op = audit::enter("List::dedup");
+    self.dedup_by(|a, b| a == b)
+  }
 
-    list.push_front(1);
-    list.push_front(2);
-    list.push_front(3);
+  // same_bucket gets (current, last kept) like Vec::dedup_by, and the duplicates get unlinked as we find them.
+  pub fn dedup_by<F>(&mut self, mut same_bucket: F) where F: FnMut(&mut T, &mut T) -> bool {
+    let _op = audit::enter("List::dedup_by");
+    let mut kept = match self.head.clone() {
+      Some(head) => head,
+      None => return,
+    };
+    let mut cur = kept.borrow().next.clone();
+    while let Some(node) = cur {
+      let dup = same_bucket(&mut node.borrow_mut().elem, &mut kept.borrow_mut().elem);
+      cur = node.borrow().next.clone();
+      if dup {
+        self.unlink(node);
+      } else {
+        kept = node;
+      }
+    }
+  }
+
+  // Sends the first k elements round to the back. It's just a split and an append, so nothing moves but a few
+  // links, and the walk to the split comes in from whichever end is nearer.
+  pub fn rotate_left(&mut self, k: usize) {
+    let _op = audit::enter("List::rotate_left");
+    assert!(k <= self.len, "rotation by {} out of bounds for a list of length {}", k, self.len);
+    let mut back = self.split_off(k);
+    back.append(self);
+    *self = back;
+  }
+
+  // And the last k round to the front.
+  pub fn rotate_right(&mut self, k: usize) {
+    let _op = audit::enter("List::rotate_right");
+    assert!(k <= self.len, "rotation by {} out of bounds for a list of length {}", k, self.len);
+    self.rotate_left(self.len - k);
+  }
+
+  // Trades the values at i and j, leaving the nodes where they are. Handles follow nodes, not values, so a handle
+  // to the node at i sees j's old value afterwards.
+  pub fn swap(&mut self, i: usize, j: usize) {
+    let _op = audit::enter("List::swap");
+    assert!(i < self.len && j < self.len, "swap indices ({}, {}) out of bounds for a list of length {}", i, j, self.len);
+    if i == j {
+      return;
+    }
+    let (a, b) = (self.node_at(i), self.node_at(j));
+    std::mem::swap(&mut a.borrow_mut().elem, &mut b.borrow_mut().elem);
+  }
+
+  // Splits the list in two, keeping the order within each: (the ones pred likes, the rest). The nodes themselves
+  // move across, so no T gets cloned or even moved, and handles keep working in whichever list their node ends
+  // up in.
+  pub fn partition<F>(mut self, mut pred: F) -> (Self, Self) where F: FnMut(&T) -> bool {
+    let _op = audit::enter("List::partition");
+    let (mut yes, mut no) = (List::with_cell(), List::with_cell());
+    while let Some(node) = self.head.clone() {
+      let keep = pred(&node.borrow().elem);
+      self.detach(&node);
+      let list = if keep { &mut yes } else { &mut no };
+      node.borrow().owner.store(list.id, Ordering::Relaxed);
+      list.push_back_node(node);
+    }
+    (yes, no)
+  }
+
+  // Takes out the node a handle points at. None if it's already gone, popped or removed or dropped with its list.
+  pub fn remove_node(&mut self, handle: &NodeHandle<T, K>) -> Option<T> {
+    let _op = audit::enter("List::remove_node");
+    let node = self.resolve(handle)?;
+    Some(self.unlink(node))
+  }
+
+  // Unhooks the node from wherever it is and puts it back at the front. Same node, so the handle stays good.
+  // Returns false if the node's already gone.
+  pub fn move_to_front(&mut self, handle: &NodeHandle<T, K>) -> bool {
+    let _op = audit::enter("List::move_to_front");
+    let node = match self.resolve(handle) {
+      Some(node) => node,
+      None => return false,
+    };
+    self.detach(&node);
+    self.push_front_node(node);
+    true
+  }
+
+  // The other way round, for when the back's the end you want recent things at.
+  pub fn move_to_back(&mut self, handle: &NodeHandle<T, K>) -> bool {
+    let _op = audit::enter("List::move_to_back");
+    let node = match self.resolve(handle) {
+      Some(node) => node,
+      None => return false,
+    };
+    self.detach(&node);
+    self.push_back_node(node);
+    true
+  }
+
+  // Gets the node behind a handle, or None if it's gone. If it's still around but in some other list we panic:
+  // carrying on would leave both lists pointing at each other's nodes, with neither len right.
+  //
+  // The owner gets checked before we go anywhere near the node. Upgrading someone else's node, even for a moment,
+  // would get in the way of their into_inner on another thread, and borrowing it would clash with any guard
+  // they've got out on it.
+  fn resolve(&self, handle: &NodeHandle<T, K>) -> Option<Shared<T, K>> {
+    let owner = handle.1.upgrade()?;
+    assert!(owner.load(Ordering::Relaxed) == self.id, "node belongs to another list");
+    K::upgrade(&handle.0)
+  }
+
+  // Stitches node's neighbours (or our head/tail) together around it, leaving it on its own. The node had better
+  // be one of ours.
+  fn detach(&mut self, node: &Shared<T, K>) {
+    let prev = node.borrow_mut().prev.take().and_then(|prev| K::upgrade(&prev));
+    let next = node.borrow_mut().next.take();
+    match prev {
+      Some(ref prev) => prev.borrow_mut().next = next.clone(),
+      None => self.head = next.clone(),
+    }
+    match next {
+      Some(ref next) => next.borrow_mut().prev = prev.as_ref().map(K::downgrade),
+      None => self.tail = prev,
+    }
+    self.len -= 1;
+  }
+
+  // Once it's detached, the Rc we were handed should be the last one standing, so we can take the element back out.
+  fn unlink(&mut self, node: Shared<T, K>) -> T {
+    self.detach(&node);
+    K::into_inner(node).ok().unwrap().elem
+  }
+
+  pub fn into_vec(mut self) -> Vec<T> {
+    let _op = audit::enter("List::into_vec");
+    let mut vec = Vec::with_capacity(self.len);
+    vec.extend(self.drain());
+    vec
+  }
+
+  // With both links strong we could just swap them, but next owns and prev doesn't, so the ownership has to turn
+  // around too. We carry the node we've just flipped along with us, and the next one down the line makes it its
+  // new next, which takes over owning it.
+  pub fn reverse(&mut self) {
+    let _op = audit::enter("List::reverse");
+    self.tail = self.head.clone();
+    let mut prev: Link<T, K> = None;
+    let mut cur = self.head.take();
+    while let Some(node) = cur {
+      let next = {
+        let mut node_mut = node.borrow_mut();
+        let next = node_mut.next.take();
+        node_mut.prev = next.as_ref().map(K::downgrade);
+        node_mut.next = prev;
+        next
+      };
+      prev = Some(node);
+      cur = next;
+    }
+    self.head = prev;
+  }
+
+  // The iterator the Ref problem won't let us write, minus the refs: each element gets cloned out while its node
+  // is borrowed, and the borrow's over before we hand it back.
+  pub fn iter_cloned(&self) -> IterCloned<'_, T, K> where T: Clone {
+    IterCloned { front: self.head.clone(), back: self.tail.clone(), len: self.len, marker: PhantomData }
+  }
+
+  // Display with something other than a space between the elements, e.g. list.separated_by(", ").
+  pub fn separated_by<'a>(&'a self, separator: &'a str) -> Separated<'a, T, K> {
+    Separated { list: self, separator }
+  }
+
+  // Pops lazily as it's advanced, and whatever's left goes when the Drain does, so the list is empty afterwards
+  // either way.
+  pub fn drain(&mut self) -> Drain<'_, T, K> {
+    Drain { list: self }
+  }
+
+  // A real Iter would have to hand out a Ref to each node that outlives the Ref we used to reach it, and RefCell
+  // just won't let us do that. So we flip it around: we do the walking, and f gets each element while that one
+  // node is borrowed. We hold our own Rc to the node we're on, so nothing we borrow depends on the previous node.
+  pub fn for_each<F>(&self, mut f: F) where F: FnMut(&T) {
+    let _op = audit::enter("List::for_each");
+    let mut cur = self.head.clone();
+    while let Some(node) = cur {
+      let node = node.borrow();
+      f(&node.elem);
+      cur = node.next.clone();
+    }
+  }
+
+  // for_each, but f gets to change each element. Each node is borrowed mutably just for its own call.
+  pub fn for_each_mut<F>(&mut self, mut f: F) where F: FnMut(&mut T) {
+    let _op = audit::enter("List::for_each_mut");
+    let mut cur = self.head.clone();
+    while let Some(node) = cur {
+      let mut node = node.borrow_mut();
+      f(&mut node.elem);
+      cur = node.next.clone();
+    }
+  }
+}
+
+// The plain methods above panic if a node they need is already borrowed. The borrow checker keeps that from
+// happening through our own API, but a leaked guard (mem::forget on a Ref, say) can still wedge a node, so these
+// check every node they'd touch up front and hand back the error instead. RefCell isn't Sync, so nothing can
+// sneak in between the check and the real operation.
+impl<T> List<T> {
+  pub fn try_push_front(&mut self, elem: T) -> Result<NodeHandle<T>, BorrowMutError> {
+    let _op = audit::enter("List::try_push_front");
+    check_mut(&self.head)?;
+    Ok(self.push_front(elem))
+  }
+
+  pub fn try_push_back(&mut self, elem: T) -> Result<NodeHandle<T>, BorrowMutError> {
+    let _op = audit::enter("List::try_push_back");
+    check_mut(&self.tail)?;
+    Ok(self.push_back(elem))
+  }
+
+  pub fn try_pop_front(&mut self) -> Result<Option<T>, BorrowMutError> {
+    let _op = audit::enter("List::try_pop_front");
+    if let Some(ref head) = self.head {
+      check_mut(&head.try_borrow_mut()?.next)?;
+    }
+    Ok(self.pop_front())
+  }
+
+  pub fn try_pop_back(&mut self) -> Result<Option<T>, BorrowMutError> {
+    let _op = audit::enter("List::try_pop_back");
+    if let Some(ref tail) = self.tail {
+      check_mut(&tail.try_borrow_mut()?.prev.as_ref().and_then(|prev| prev.upgrade()))?;
+    }
+    Ok(self.pop_back())
+  }
+
+  pub fn try_peek_front(&self) -> Result<Option<Ref<'_, T>>, BorrowError> {
+    let _op = audit::enter("List::try_peek_front");
+    try_peek(&self.head)
+  }
+
+  pub fn try_peek_back(&self) -> Result<Option<Ref<'_, T>>, BorrowError> {
+    let _op = audit::enter("List::try_peek_back");
+    try_peek(&self.tail)
+  }
+
+  pub fn try_peek_front_mut(&mut self) -> Result<Option<RefMut<'_, T>>, BorrowMutError> {
+    let _op = audit::enter("List::try_peek_front_mut");
+    try_peek_mut(&self.head)
+  }
+
+  pub fn try_peek_back_mut(&mut self) -> Result<Option<RefMut<'_, T>>, BorrowMutError> {
+    let _op = audit::enter("List::try_peek_back_mut");
+    try_peek_mut(&self.tail)
+  }
+}
+
+fn check_mut<T>(link: &Link<T, Checked>) -> Result<(), BorrowMutError> {
+  match link {
+    Some(node) => node.try_borrow_mut().map(|_| ()),
+    None => Ok(()),
+  }
+}
+
+fn try_peek<T>(link: &Link<T, Checked>) -> Result<Option<Ref<'_, T>>, BorrowError> {
+  match link {
+    Some(node) => node.try_borrow().map(|node| Some(Ref::map(node, |node| &node.elem))),
+    None => Ok(None),
+  }
+}
+
+fn try_peek_mut<T>(link: &Link<T, Checked>) -> Result<Option<RefMut<'_, T>>, BorrowMutError> {
+  match link {
+    Some(node) => node.try_borrow_mut().map(|node| Some(RefMut::map(node, |node| &mut node.elem))),
+    None => Ok(None),
+  }
+}
+
+impl<T, K: Kind> List<T, K> {
+  pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, K> {
+    let cur = self.head.clone();
+    let index = cur.as_ref().map(|_| 0);
+    CursorMut { list: self, cur, index }
+  }
+
+  pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, K> {
+    let cur = self.tail.clone();
+    let index = cur.as_ref().map(|_| self.len - 1);
+    CursorMut { list: self, cur, index }
+  }
+}
+
+// Same deal as std's CursorMut: the cursor sits on an element, or on a "ghost" spot that lives between the tail
+// and the head, so walking off either end lands there and walking on wraps around to the other end. Holding our
+// own Rc to the current node is what lets us get at its neighbours without a chain of borrows back to the list.
+pub struct CursorMut<'a, T, K: Kind = Checked> {
+  list: &'a mut List<T, K>,
+  cur: Link<T, K>,
+  index: Option<usize>,
+}
+
+// Does nothing, but having it at all keeps the list borrowed until the cursor's actually dropped. Without it the
+// borrow would end at the cursor's last use, while its Rc on the current node hung about until the end of the
+// scope, and a pop in between would find that node still shared.
+impl<'a, T, K: Kind> Drop for CursorMut<'a, T, K> {
+  fn drop(&mut self) {}
+}
+
+impl<'a, T, K: Kind> CursorMut<'a, T, K> {
+  pub fn index(&self) -> Option<usize> {
+    self.index
+  }
+
+  pub fn move_next(&mut self) {
+    let _op = audit::enter("CursorMut::move_next");
+    match self.cur.take() {
+      Some(node) => {
+        self.cur = node.borrow().next.clone();
+        self.index = match self.cur {
+          Some(_) => self.index.map(|i| i + 1),
+          None => None,
+        };
+      }
+      None => {
+        self.cur = self.list.head.clone();
+        self.index = self.cur.as_ref().map(|_| 0);
+      }
+    }
+  }
+
+  pub fn move_prev(&mut self) {
+    let _op = audit::enter("CursorMut::move_prev");
+    match self.cur.take() {
+      Some(node) => {
+        self.cur = node.borrow().prev.as_ref().and_then(K::upgrade);
+        self.index = match self.cur {
+          Some(_) => self.index.map(|i| i - 1),
+          None => None,
+        };
+      }
+      None => {
+        self.cur = self.list.tail.clone();
+        self.index = self.cur.as_ref().map(|_| self.list.len - 1);
+      }
+    }
+  }
+
+  pub fn current(&mut self) -> Option<K::RefMut<'_, T>> {
+    let _op = audit::enter("CursorMut::current");
+    self.cur.as_ref().map(|node| K::elem_mut(node.borrow_mut()))
+  }
+
+  // On the ghost, "before" means the end of the list.
+  pub fn insert_before(&mut self, elem: T) {
+    let _op = audit::enter("CursorMut::insert_before");
+    let cur = match self.cur {
+      Some(ref cur) => cur.clone(),
+      None => {
+        self.list.push_back(elem);
+        return;
+      }
+    };
+    self.list.insert_before_node(&cur, elem);
+    self.index = self.index.map(|i| i + 1);
+  }
+
+  // On the ghost, "after" means the front of the list.
+  pub fn insert_after(&mut self, elem: T) {
+    let _op = audit::enter("CursorMut::insert_after");
+    let cur = match self.cur {
+      Some(ref cur) => cur.clone(),
+      None => {
+        self.list.push_front(elem);
+        return;
+      }
+    };
+    let next = cur.borrow().next.clone();
+    match next {
+      Some(next) => {
+        let new = Node::<T, K>::new(elem, self.list.id);
+        new.borrow_mut().prev = Some(K::downgrade(&cur));
+        next.borrow_mut().prev = Some(K::downgrade(&new));
+        new.borrow_mut().next = Some(next);
+        cur.borrow_mut().next = Some(new);
+        self.list.len += 1;
+      }
+      None => {
+        self.list.push_back(elem);
+      }
+    }
+  }
+
+  // Takes the current element out and leaves the cursor on the one after it (or the ghost, if it was the tail).
+  pub fn remove_current(&mut self) -> Option<T> {
+    let _op = audit::enter("CursorMut::remove_current");
+    let node = self.cur.take()?;
+    let next = node.borrow().next.clone();
+    if next.is_none() {
+      self.index = None;
+    }
+    self.cur = next;
+    Some(self.list.unlink(node))
+  }
+}
+
+// Deriving would only clone the Rcs, leaving two lists fighting over one set of nodes. Build a fresh chain instead.
+impl<T: Clone, K: Kind> Clone for List<T, K> {
+  fn clone(&self) -> Self {
+    let _op = audit::enter("List::clone");
+    let mut new = List::with_cell();
+    self.for_each(|elem| { new.push_back(elem.clone()); });
+    new
+  }
+}
+
+impl<T: PartialEq, K: Kind> PartialEq for List<T, K> {
+  fn eq(&self, other: &Self) -> bool {
+    let _op = audit::enter("List::eq");
+    if self.len != other.len {
+      return false;
+    }
+    let (mut a, mut b) = (self.head.clone(), other.head.clone());
+    while let (Some(x), Some(y)) = (a, b) {
+      // comparing a list with itself borrows each node twice, but shared borrows are happy to overlap
+      let (x, y) = (x.borrow(), y.borrow());
+      if x.elem != y.elem {
+        return false;
+      }
+      a = x.next.clone();
+      b = y.next.clone();
+    }
+    true
+  }
+}
+
+impl<T: Eq, K: Kind> Eq for List<T, K> {}
+
+impl<T: fmt::Debug, K: Kind> fmt::Debug for List<T, K> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let _op = audit::enter("List::fmt");
+    let mut list = f.debug_list();
+    self.for_each(|elem| { list.entry(elem); });
+    list.finish()
+  }
+}
+
+// Front to back, space separated, like silly1's. separated_by picks a different separator.
+impl<T: fmt::Display, K: Kind> fmt::Display for List<T, K> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.separated_by(" ").fmt(f)
+  }
+}
+
+pub struct Separated<'a, T, K: Kind = Checked> {
+  list: &'a List<T, K>,
+  separator: &'a str,
+}
+
+impl<'a, T: fmt::Display, K: Kind> fmt::Display for Separated<'a, T, K> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let _op = audit::enter("List::fmt");
+    // for_each won't stop early, so once a write fails we just stop writing and pass the error on at the end
+    let mut result = Ok(());
+    let mut first = true;
+    self.list.for_each(|elem| {
+      if result.is_err() {
+        return;
+      }
+      if !first {
+        result = f.write_str(self.separator);
+      }
+      first = false;
+      if result.is_ok() {
+        result = fmt::Display::fmt(elem, f);
+      }
+    });
+    result
+  }
+}
+
+impl<T, K: Kind> Extend<T> for List<T, K> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for elem in iter {
+      self.push_back(elem);
+    }
+  }
+}
+
+impl<T, K: Kind> FromIterator<T> for List<T, K> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut list = List::with_cell();
+    list.extend(iter);
+    list
+  }
+}
+
+impl<T> From<Vec<T>> for List<T> {
+  fn from(vec: Vec<T>) -> Self {
+    vec.into_iter().collect()
+  }
+}
+
+// Same as with Vec: going in picks the default cell, so List::from doesn't need a type annotation.
+impl<T> From<LinkedList<T>> for List<T> {
+  fn from(list: LinkedList<T>) -> Self {
+    list.into_iter().collect()
+  }
+}
+
+impl<T> From<VecDeque<T>> for List<T> {
+  fn from(deque: VecDeque<T>) -> Self {
+    deque.into_iter().collect()
+  }
+}
+
+impl<T, K: Kind> From<List<T, K>> for LinkedList<T> {
+  fn from(list: List<T, K>) -> Self {
+    list.into_iter().collect()
+  }
+}
+
+impl<T, K: Kind> From<List<T, K>> for VecDeque<T> {
+  fn from(list: List<T, K>) -> Self {
+    let mut deque = VecDeque::with_capacity(list.len());
+    deque.extend(list);
+    deque
+  }
+}
+
+impl<T, K: Kind> Default for List<T, K> {
+  fn default() -> Self {
+    Self::with_cell()
+  }
+}
+
+impl<T, K: Kind> Drop for List<T, K> {
+  fn drop(&mut self) {
+    let _op = audit::enter("List::drop");
+    while self.pop_front().is_some() {}
+  }
+}
+
+pub struct IntoIter<T, K: Kind = Checked>(List<T, K>);
+
+impl<T, K: Kind> IntoIterator for List<T, K> {
+  type Item = T;
+  type IntoIter = IntoIter<T, K>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter(self)
+  }
+}
+
+impl<T, K: Kind> Iterator for IntoIter<T, K> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T>{
+    self.0.pop_front()
+  }
+}
+
+impl<T, K: Kind> DoubleEndedIterator for IntoIter<T, K> {
+  fn next_back(&mut self) -> Option<T>{
+    self.0.pop_back()
+  }
+}
+
+// The Rcs in here would stop pop from taking its node back, so we hang on to &List for as long as we've got them.
+pub struct IterCloned<'a, T, K: Kind = Checked> {
+  front: Link<T, K>,
+  back: Link<T, K>,
+  len: usize,
+  marker: PhantomData<&'a List<T, K>>,
+}
+
+// Same story as CursorMut's empty Drop.
+impl<'a, T, K: Kind> Drop for IterCloned<'a, T, K> {
+  fn drop(&mut self) {}
+}
+
+impl<'a, T: Clone, K: Kind> Iterator for IterCloned<'a, T, K> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    let _op = audit::enter("IterCloned::next");
+    if self.len == 0 {
+      return None;
+    }
+    self.len -= 1;
+    self.front.take().map(|node| {
+      let node = node.borrow();
+      self.front = node.next.clone();
+      node.elem.clone()
+    })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<'a, T: Clone, K: Kind> DoubleEndedIterator for IterCloned<'a, T, K> {
+  fn next_back(&mut self) -> Option<T> {
+    let _op = audit::enter("IterCloned::next_back");
+    if self.len == 0 {
+      return None;
+    }
+    self.len -= 1;
+    self.back.take().map(|node| {
+      let node = node.borrow();
+      self.back = node.prev.as_ref().and_then(K::upgrade);
+      node.elem.clone()
+    })
+  }
+}
+
+impl<'a, T: Clone, K: Kind> ExactSizeIterator for IterCloned<'a, T, K> {}
+
+// On the wire it's just the elements front to back. The links get rebuilt by pushing them back on one at a time.
+#[cfg(feature = "serde")]
+mod serde_impls {
+  use super::{Kind, List, NodeCell};
+  use serde::de::{Deserialize, Deserializer};
+  use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+  impl<T: Serialize, K: Kind> Serialize for List<T, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+      let _op = super::audit::enter("List::serialize");
+      // for_each can't stop early, and we need to bail on the first error, so we do the walk ourselves
+      let mut seq = serializer.serialize_seq(Some(self.len))?;
+      let mut cur = self.head.clone();
+      while let Some(node) = cur {
+        let node = node.borrow();
+        seq.serialize_element(&node.elem)?;
+        cur = node.next.clone();
+      }
+      seq.end()
+    }
+  }
+
+  impl<'de, T: Deserialize<'de>, K: Kind> Deserialize<'de> for List<T, K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+      Vec::deserialize(deserializer).map(|vec| vec.into_iter().collect())
+    }
+  }
+}
+
+pub struct Drain<'a, T, K: Kind = Checked> {
+  list: &'a mut List<T, K>,
+}
+
+impl<'a, T, K: Kind> Iterator for Drain<'a, T, K> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.list.pop_front()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.list.len, Some(self.list.len))
+  }
+}
+
+impl<'a, T, K: Kind> DoubleEndedIterator for Drain<'a, T, K> {
+  fn next_back(&mut self) -> Option<T> {
+    self.list.pop_back()
+  }
+}
+
+impl<'a, T, K: Kind> ExactSizeIterator for Drain<'a, T, K> {}
+
+impl<'a, T, K: Kind> Drop for Drain<'a, T, K> {
+  fn drop(&mut self) {
+    while self.list.pop_front().is_some() {}
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Checked, Kind, List, Locked};
+  use std::cell::{Cell, Ref, RefMut};
+  use std::rc::Rc;
+
+  #[test]
+  fn basics() {
+    let mut list = List::new();
+    // empty list behavior check
+    assert_eq!(list.pop_front(), None);
+    // populate list
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
+    // check normal removal
+    assert_eq!(list.pop_front(), Some(3));
+    assert_eq!(list.pop_front(), Some(2));
+    // push more to list, check nothing is corrupted
+    list.push_front(4);
+    list.push_front(5);
+    // check normal removal
+    assert_eq!(list.pop_front(), Some(5));
+    assert_eq!(list.pop_front(), Some(4));
+    // check exhaustion
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_front(), None);
+
+    // ------ Back ---------
+
+    // check empty list behaves itself
+    assert_eq!(list.pop_back(), None);
+
+    // populate list
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+
+    // check normal removal
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_back(), Some(2));
+
+    // Push some more just to make sure nothing's corrupted
+    list.push_back(4);
+    list.push_back(5);
+
+    // check normal removal
+    assert_eq!(list.pop_back(), Some(5));
+    assert_eq!(list.pop_back(), Some(4));
+
+    // check exhaustion
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_back(), None);
+  }
+
+  #[test]
+  fn deque() {
+    let mut list = List::new();
+
+    // feed both ends and drain from both ends
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+    list.push_front(0);
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(0));
+
+    // down to one node, it's both the head and the tail
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_front(), None);
+
+    // and the ends still line up after running dry from the back
+    list.push_front(4);
+    assert_eq!(list.pop_back(), Some(4));
+    list.push_back(5);
+    assert_eq!(list.pop_front(), Some(5));
+    assert_eq!(list.pop_back(), None);
+  }
+
+  #[test]
+  fn len() {
+    let mut list = List::new();
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+
+    list.push_back(1);
+    list.push_front(0);
+    list.push_back(2);
+    assert_eq!(list.len(), 3);
+    assert!(!list.is_empty());
+
+    list.pop_front();
+    list.pop_back();
+    assert_eq!(list.len(), 1);
+
+    // popping an empty list doesn't wrap the count around
+    list.pop_back();
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn peek() {
+    let mut list = List::new();
+    assert!(list.peek_front().is_none());
+    assert!(list.peek_back().is_none());
+    assert!(list.peek_front_mut().is_none());
+    assert!(list.peek_back_mut().is_none());
+
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
+
+    assert_eq!(&*list.peek_front().unwrap(), &3);
+    assert_eq!(&mut *list.peek_front_mut().unwrap(), &mut 3);
+    assert_eq!(&*list.peek_back().unwrap(), &1);
+    assert_eq!(&mut *list.peek_back_mut().unwrap(), &mut 1);
+  }
+
+  #[test]
+  fn peek_guards() {
+    let mut list = List::new();
+    list.push_back(1);
+
+    // one node is both ends, and shared guards on it can live side by side
+    {
+      let front = list.peek_front().unwrap();
+      let back = list.peek_back().unwrap();
+      assert_eq!((*front, *back), (1, 1));
+    }
+
+    list.push_back(2);
+    list.push_front(0);
+    assert_eq!(*list.peek_front().unwrap(), 0);
+    assert_eq!(*list.peek_back().unwrap(), 2);
+
+    // guards are gone, so the list can be changed again
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(*list.peek_back().unwrap(), 1);
+  }
+
+  #[test]
+  fn peek_mut_in_place() {
+    let mut list = List::new();
+    list.push_back(0);
+    list.push_back(10);
+
+    // bump a counter at the head without popping it
+    for _ in 0..3 {
+      *list.peek_front_mut().unwrap() += 1;
+    }
+    if let Some(mut back) = list.peek_back_mut() {
+      *back *= 2;
+    }
+
+    assert_eq!(list.pop_front(), Some(3));
+    assert_eq!(list.pop_front(), Some(20));
+  }
+
+  #[test]
+  fn checked_guards_are_std() {
+    // the default kind hands out real std guards, so everything Ref and RefMut can do works on them
+    let mut list = List::from(vec![(1, 'a'), (2, 'b')]);
+    {
+      let front: Ref<'_, (i32, char)> = list.peek_front().unwrap();
+      let (num, letter) = Ref::map_split(Ref::clone(&front), |pair| (&pair.0, &pair.1));
+      assert_eq!((*num, *letter), (1, 'a'));
+      assert_eq!(*Ref::map(list.get(1).unwrap(), |pair| &pair.0), 2);
+      assert_eq!(*Ref::map(list.find(|pair| pair.1 == 'b').unwrap(), |pair| &pair.1), 'b');
+    }
+    *RefMut::map(list.peek_back_mut().unwrap(), |pair| &mut pair.1) = 'z';
+    assert_eq!(list.pop_back(), Some((2, 'z')));
+  }
+
+  #[test]
+  fn for_each() {
+    let mut list = List::new();
+    list.for_each(|_: &i32| panic!("empty list has nothing to visit"));
+
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+
+    let mut seen = Vec::new();
+    list.for_each(|&x| seen.push(x));
+    assert_eq!(seen, vec![1, 2, 3]);
+
+    // the list survives the visit, and peeking while visiting is fine
+    list.for_each(|_| assert_eq!(*list.peek_front().unwrap(), 1));
+    assert_eq!(list.pop_back(), Some(3));
+  }
+
+  #[test]
+  fn cursor_walk() {
+    let mut list = List::new();
+    {
+      let mut cursor = list.cursor_front_mut();
+      assert!(cursor.current().is_none());
+      assert_eq!(cursor.index(), None);
+      cursor.move_next();
+      assert!(cursor.current().is_none());
+    }
+
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(*cursor.current().unwrap(), 1);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(*cursor.current().unwrap(), 3);
+
+    // off the end onto the ghost, and round to the front again
+    cursor.move_next();
+    assert!(cursor.current().is_none());
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    assert_eq!(*cursor.current().unwrap(), 1);
+
+    // and the same going backwards
+    cursor.move_prev();
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.index(), Some(2));
+    *cursor.current().unwrap() = 30;
+    cursor.move_prev();
+    assert_eq!(*cursor.current().unwrap(), 2);
+    drop(cursor);
+
+    let mut cursor = list.cursor_back_mut();
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(*cursor.current().unwrap(), 30);
+  }
+
+  #[test]
+  fn cursor_edits() {
+    let mut list = List::new();
+    list.push_back(2);
+    list.push_back(4);
+
+    {
+      let mut cursor = list.cursor_front_mut();
+      cursor.insert_before(1);
+      cursor.insert_after(3);
+      assert_eq!(cursor.index(), Some(1));
+      assert_eq!(*cursor.current().unwrap(), 2);
+
+      cursor.move_next();
+      cursor.move_next();
+      cursor.insert_after(5);
+      cursor.move_next();
+      assert_eq!(cursor.index(), Some(4));
+      assert_eq!(*cursor.current().unwrap(), 5);
+
+      // the ghost inserts at the ends
+      cursor.move_next();
+      cursor.insert_before(6);
+      cursor.insert_after(0);
+      assert_eq!(cursor.index(), None);
+    }
+    assert_eq!(list.len(), 7);
+    assert_eq!(*list.peek_front().unwrap(), 0);
+    assert_eq!(*list.peek_back().unwrap(), 6);
+
+    {
+      let mut cursor = list.cursor_front_mut();
+      cursor.move_next();
+      cursor.move_next();
+      assert_eq!(cursor.remove_current(), Some(2));
+      assert_eq!(cursor.index(), Some(2));
+      assert_eq!(*cursor.current().unwrap(), 3);
+      drop(cursor);
+
+      let mut cursor = list.cursor_front_mut();
+      assert_eq!(cursor.remove_current(), Some(0));
+      assert_eq!(cursor.index(), Some(0));
+      drop(cursor);
+
+      let mut cursor = list.cursor_back_mut();
+      assert_eq!(cursor.remove_current(), Some(6));
+      assert_eq!(cursor.index(), None);
+      assert_eq!(cursor.remove_current(), None);
+    }
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.pop_back(), Some(5));
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 3, 4]);
+  }
+
+  #[test]
+  fn append_prepend() {
+    let mut list = List::new();
+    let mut other = List::new();
+
+    // empty onto empty, and empty onto something, are both no-ops
+    list.append(&mut other);
+    assert!(list.is_empty());
+    list.push_back(1);
+    list.append(&mut other);
+    list.prepend(&mut other);
+    assert_eq!(list.len(), 1);
+
+    other.push_back(2);
+    other.push_back(3);
+    list.append(&mut other);
+    assert!(other.is_empty());
+    assert!(other.peek_front().is_none() && other.peek_back().is_none());
+    assert_eq!(list.len(), 3);
+    assert_eq!(*list.peek_back().unwrap(), 3);
+
+    other.push_back(-1);
+    other.push_back(0);
+    list.prepend(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(list.len(), 5);
+
+    // the seams link up both ways
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(*list.peek_back().unwrap(), 0);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![-1, 0]);
+
+    // and the drained list is still perfectly usable
+    other.push_front(7);
+    let mut list = List::new();
+    list.append(&mut other);
+    assert_eq!(list.pop_front(), Some(7));
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn split_off() {
+    let mut list = List::new();
+    for i in 0..6 {
+      list.push_back(i);
+    }
+
+    // from the back half and from the front half, to exercise both walks
+    let mut back = list.split_off(4);
+    let mut mid = list.split_off(1);
+    assert_eq!((list.len(), mid.len(), back.len()), (1, 3, 2));
+    assert_eq!((*list.peek_front().unwrap(), *list.peek_back().unwrap()), (0, 0));
+    assert_eq!((*mid.peek_front().unwrap(), *mid.peek_back().unwrap()), (1, 3));
+    assert_eq!((*back.peek_front().unwrap(), *back.peek_back().unwrap()), (4, 5));
+
+    // both halves' ends are really cut loose
+    assert_eq!(mid.pop_back(), Some(3));
+    assert_eq!(mid.pop_back(), Some(2));
+    assert_eq!(mid.pop_back(), Some(1));
+    assert_eq!(mid.pop_back(), None);
+    assert_eq!(back.pop_front(), Some(4));
+    assert_eq!(back.pop_front(), Some(5));
+
+    // the edges: splitting at 0 takes everything, at len takes nothing
+    list.push_back(1);
+    let all = list.split_off(0);
+    assert!(list.is_empty());
+    assert_eq!(all.len(), 2);
+    let mut all = all;
+    assert!(all.split_off(2).is_empty());
+    assert_eq!(all.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+  }
+
+  #[test]
+  #[should_panic(expected = "split index 2 out of bounds for a list of length 1")]
+  fn split_off_past_end() {
+    let mut list = List::new();
+    list.push_back(0);
+    list.split_off(2);
+  }
+
+  #[test]
+  fn splice_at() {
+    let mut list: List<_> = vec![0, 4].into();
+    list.splice_at(1, vec![1, 2, 3].into());
+    list.splice_at(0, vec![-1].into());
+    list.splice_at(6, vec![5].into());
+    list.splice_at(3, List::new());
+    assert_eq!(list.len(), 7);
+    assert_eq!(list.iter_cloned().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4, 5]);
+    // the back links got stitched too
+    assert_eq!(list.iter_cloned().rev().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1, 0, -1]);
+  }
+
+  #[test]
+  #[should_panic(expected = "splice index 2 out of bounds for a list of length 1")]
+  fn splice_at_past_end() {
+    let mut list = List::new();
+    list.push_back(0);
+    list.splice_at(2, vec![1].into());
+  }
+
+  #[test]
+  fn insert_remove() {
+    let mut list = List::new();
+    list.insert(0, 2);
+    list.insert(0, 0);
+    list.insert(2, 4);
+    list.insert(1, 1);
+    list.insert(3, 3);
+    assert_eq!(list.len(), 5);
+
+    // from the far end of each half
+    assert_eq!(list.remove(3), Some(3));
+    assert_eq!(list.remove(1), Some(1));
+    assert_eq!(list.remove(3), None);
+    assert_eq!(list.len(), 3);
+
+    // the ends go through the same path
+    assert_eq!(list.remove(2), Some(4));
+    assert_eq!(*list.peek_back().unwrap(), 2);
+    assert_eq!(list.remove(0), Some(0));
+    assert_eq!(*list.peek_front().unwrap(), 2);
+    assert_eq!(list.remove(0), Some(2));
+    assert!(list.is_empty());
+    assert!(list.peek_front().is_none() && list.peek_back().is_none());
+    assert_eq!(list.remove(0), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "insertion index 1 out of bounds for a list of length 0")]
+  fn insert_past_end() {
+    let mut list = List::new();
+    list.insert(1, 0);
+  }
+
+  #[test]
+  fn retain() {
+    let mut list = List::new();
+    list.retain(|_: &i32| panic!("empty list has nothing to test"));
+
+    for i in 0..10 {
+      list.push_back(i);
+    }
+    // drops both ends along with a run in the middle
+    list.retain(|&x| x % 3 == 1 || x == 5);
+    assert_eq!(list.len(), 4);
+    assert_eq!(*list.peek_front().unwrap(), 1);
+    assert_eq!(*list.peek_back().unwrap(), 7);
+    assert_eq!(list.pop_back(), Some(7));
+    assert_eq!(list.pop_back(), Some(5));
+
+    list.retain(|_| false);
+    assert!(list.is_empty());
+    assert!(list.peek_front().is_none() && list.peek_back().is_none());
+  }
+
+  #[test]
+  fn retain_drops() {
+    let drops = Rc::new(Cell::new(0));
+    let mut list = List::new();
+    for _ in 0..6 {
+      list.push_back(Tracked(drops.clone()));
+    }
+
+    let mut n = 0;
+    list.retain(|_| { n += 1; n % 2 == 0 });
+    assert_eq!(drops.get(), 3);
+    drop(list);
+    assert_eq!(drops.get(), 6);
+  }
+
+  #[test]
+  fn contains_find() {
+    let mut list = List::new();
+    assert!(!list.contains(&1));
+    assert!(list.find(|_| true).is_none());
+
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+    list.push_back(4);
+    assert!(list.contains(&1) && list.contains(&4));
+    assert!(!list.contains(&5));
+
+    // first match wins, and its guard can be held alongside others
+    let even = list.find(|x| x % 2 == 0).unwrap();
+    let back = list.peek_back().unwrap();
+    assert_eq!((*even, *back), (2, 4));
+    drop((even, back));
+    assert!(list.find(|&x| x > 4).is_none());
+
+    // nothing got popped along the way
+    assert_eq!(list.len(), 4);
+  }
+
+  #[test]
+  fn clone() {
+    let mut list = List::new();
+    assert!(list.clone().is_empty());
+
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+    let mut copy = list.clone();
+    assert_eq!(copy.len(), 3);
+
+    // changes to one never show up in the other
+    *copy.peek_front_mut().unwrap() = 10;
+    copy.push_back(4);
+    list.pop_back();
+    assert_eq!(*list.peek_front().unwrap(), 1);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(copy.into_iter().collect::<Vec<_>>(), vec![10, 2, 3, 4]);
+  }
+
+  #[test]
+  fn eq() {
+    let mut a = List::new();
+    let mut b = List::new();
+    assert!(a == b);
+
+    a.push_back(1);
+    a.push_back(2);
+    b.push_front(2);
+    b.push_front(1);
+    assert!(a == b);
+    assert!(a == a);
+
+    // same length, different contents, and a prefix
+    *b.peek_back_mut().unwrap() = 3;
+    assert!(a != b);
+    b.pop_back();
+    assert!(a != b);
+
+    // comparing didn't use anything up
+    assert_eq!(a.len(), 2);
+  }
+
+  #[test]
+  fn debug() {
+    let mut list = List::new();
+    assert_eq!(format!("{:?}", list), "[]");
+
+    list.push_back("b");
+    list.push_front("a");
+    list.push_back("c");
+    assert_eq!(format!("{:?}", list), r#"["a", "b", "c"]"#);
+    assert_eq!(format!("{:#?}", list), "[\n    \"a\",\n    \"b\",\n    \"c\",\n]");
+
+    // printing while a guard is out is fine, both only borrow
+    let front = list.peek_front().unwrap();
+    assert_eq!(format!("{:?} {:?}", *front, list), r#""a" ["a", "b", "c"]"#);
+  }
+
+  #[test]
+  fn extend_collect() {
+    let mut list: List<i32> = (1..4).collect();
+    assert_eq!(list.len(), 3);
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+
+    list.extend(vec![4, 5]);
+    list.extend(None);
+    assert_eq!(list.len(), 5);
+    assert_eq!(*list.peek_back().unwrap(), 5);
+
+    // round trip through our own into_iter
+    let back: List<_> = list.into_iter().rev().collect();
+    assert_eq!(format!("{:?}", back), "[5, 4, 3, 2, 1]");
+  }
+
+  #[test]
+  fn drain() {
+    let mut list: List<_> = (1..6).collect();
+
+    let mut drain = list.drain();
+    assert_eq!(drain.len(), 5);
+    assert_eq!(drain.next(), Some(1));
+    assert_eq!(drain.next_back(), Some(5));
+    assert_eq!(drain.len(), 3);
+    // dropped half way, the rest goes with it
+    drop(drain);
+    assert!(list.is_empty());
+    assert!(list.peek_front().is_none());
+
+    list.extend(vec![6, 7]);
+    assert_eq!(list.drain().collect::<Vec<_>>(), vec![6, 7]);
+    assert_eq!(list.drain().next(), None);
+
+    // and the list can be filled again afterwards
+    list.push_back(8);
+    assert_eq!(list.len(), 1);
+  }
+
+  #[test]
+  fn drain_drops() {
+    let drops = Rc::new(Cell::new(0));
+    let mut list = List::new();
+    for _ in 0..4 {
+      list.push_back(Tracked(drops.clone()));
+    }
+    let kept = list.drain().next();
+    assert_eq!(drops.get(), 3);
+    drop(kept);
+    assert_eq!(drops.get(), 4);
+  }
+
+  #[test]
+  fn reverse() {
+    let mut list: List<i32> = List::new();
+    list.reverse();
+    assert!(list.is_empty());
+
+    list.push_back(1);
+    list.reverse();
+    assert_eq!(format!("{:?}", list), "[1]");
+
+    list.extend(vec![2, 3, 4]);
+    list.reverse();
+    assert_eq!(format!("{:?}", list), "[4, 3, 2, 1]");
+    assert_eq!(list.len(), 4);
+
+    // both directions of the chain were turned around, not just head and tail
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_front(), Some(4));
+    assert_eq!(*list.peek_back().unwrap(), 3);
+
+    list.extend(vec![5, 6]);
+    list.reverse();
+    assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![3, 5, 6]);
+  }
+
+  #[test]
+  fn vec_conversions() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.len(), 3);
+    assert_eq!(*list.peek_front().unwrap(), 1);
+    assert_eq!(*list.peek_back().unwrap(), 3);
+    assert_eq!(list.into_vec(), vec![1, 2, 3]);
+
+    let empty: List<i32> = Vec::new().into();
+    assert!(empty.is_empty());
+    assert_eq!(empty.into_vec(), Vec::<i32>::new());
+  }
+
+  #[test]
+  fn try_ops() {
+    let mut list = List::new();
+    assert!(list.try_peek_front().unwrap().is_none());
+    assert!(list.try_peek_back_mut().unwrap().is_none());
+    assert_eq!(list.try_pop_front().unwrap(), None);
+    assert_eq!(list.try_pop_back().unwrap(), None);
+
+    list.try_push_back(2).unwrap();
+    list.try_push_front(1).unwrap();
+    list.try_push_back(3).unwrap();
+    assert_eq!(*list.try_peek_front().unwrap().unwrap(), 1);
+    *list.try_peek_back_mut().unwrap().unwrap() = 30;
+    assert_eq!(list.try_pop_back().unwrap(), Some(30));
+    assert_eq!(list.try_pop_front().unwrap(), Some(1));
+    assert_eq!(list.len(), 1);
+  }
+
+  // Miri counts the wedged node as a leak and fails the run, and there's no getting it back: the borrow that
+  // wedges it has to be forgotten, since holding a real guard would stop us calling the try_ methods at all.
+  #[test]
+  #[cfg_attr(miri, ignore)]
+  fn try_ops_with_a_wedged_node() {
+    let mut list = List::from(vec![1, 2, 3]);
+
+    // leak a shared borrow of the head, so it stays borrowed for good
+    std::mem::forget(list.peek_front());
+
+    assert!(list.try_push_front(0).is_err());
+    assert!(list.try_pop_front().is_err());
+    assert!(list.try_peek_front_mut().is_err());
+    // shared borrows still get along with it
+    assert_eq!(*list.try_peek_front().unwrap().unwrap(), 1);
+
+    // the failed calls left everything as it was
+    assert_eq!(list.len(), 3);
+    assert_eq!(*list.peek_front().unwrap(), 1);
+
+    // the far end doesn't care until it needs the head as a neighbour
+    assert_eq!(list.try_pop_back().unwrap(), Some(3));
+    assert!(list.try_pop_back().is_err());
+    assert_eq!(list.len(), 2);
+    list.try_push_back(4).unwrap();
+
+    // the wedged node can never be freed, so let it leak instead of panicking in Drop
+    std::mem::forget(list);
+  }
+
+  #[test]
+  fn handles() {
+    let mut list = List::new();
+    let one = list.push_back(1);
+    let two = list.push_back(2);
+    let zero = list.push_front(0);
+    let three = list.push_back(3);
+
+    // from the middle, then the two ends
+    assert_eq!(list.remove_node(&two), Some(2));
+    assert_eq!(list.remove_node(&zero), Some(0));
+    assert_eq!(list.remove_node(&three), Some(3));
+    assert_eq!(list.len(), 1);
+    assert_eq!((*list.peek_front().unwrap(), *list.peek_back().unwrap()), (1, 1));
+
+    // a handle to something that's gone does nothing
+    assert_eq!(list.remove_node(&two), None);
+    assert!(!list.move_to_front(&three));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.remove_node(&one), None);
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn move_to_front() {
+    let mut list = List::new();
+    let handles: Vec<_> = (0..4).map(|i| list.push_back(i)).collect();
+
+    // already there
+    assert!(list.move_to_front(&handles[0]));
+    assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+
+    assert!(list.move_to_front(&handles[2]));
+    assert_eq!(list, List::from(vec![2, 0, 1, 3]));
+    assert!(list.move_to_front(&handles[3]));
+    assert_eq!(list, List::from(vec![3, 2, 0, 1]));
+    assert_eq!(*list.peek_back().unwrap(), 1);
+    assert_eq!(list.len(), 4);
+
+    // the handles still point at the same elements after moving
+    assert_eq!(list.remove_node(&handles[2]), Some(2));
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_back(), Some(0));
+    assert_eq!(list.pop_back(), Some(3));
+  }
+
+  #[test]
+  fn move_to_back() {
+    let mut list = List::new();
+    let handles: Vec<_> = (0..4).map(|i| list.push_back(i)).collect();
+
+    assert!(list.move_to_back(&handles[3]));
+    assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+    assert!(list.move_to_back(&handles[0]));
+    assert!(list.move_to_back(&handles[2]));
+    assert_eq!(list, List::from(vec![1, 3, 0, 2]));
+    assert_eq!(list.iter_cloned().rev().collect::<Vec<_>>(), vec![2, 0, 3, 1]);
+
+    // and back to the front again, same node all the way
+    assert!(list.move_to_front(&handles[2]));
+    assert_eq!(list.remove_node(&handles[2]), Some(2));
+    assert_eq!(list, List::from(vec![1, 3, 0]));
+
+    assert!(!list.move_to_back(&handles[2]));
+  }
+
+  #[test]
+  fn lru() {
+    // the sort of thing handles are for: a tiny LRU of the last three keys, most recent at the front
+    let mut recent = List::new();
+    let mut index = std::collections::HashMap::new();
+    for key in "abcadbe".chars() {
+      match index.get(&key) {
+        Some(handle) => { recent.move_to_front(handle); }
+        None => {
+          index.insert(key, recent.push_front(key));
+          if recent.len() > 3 {
+            let evicted = recent.pop_back().unwrap();
+            index.remove(&evicted);
+          }
+        }
+      }
+    }
+    assert_eq!(recent.into_vec(), vec!['e', 'b', 'd']);
+  }
+
+  #[test]
+  #[should_panic(expected = "node belongs to another list")]
+  fn handle_from_another_list() {
+    let mut a = List::new();
+    let mut b = List::new();
+    a.push_back(0);
+    let handle = b.push_back(1);
+    a.remove_node(&handle);
+  }
+
+  #[test]
+  #[should_panic(expected = "node belongs to another list")]
+  fn handle_from_another_list_while_borrowed() {
+    // the owner check mustn't touch the node, or b's guard turns it into "already mutably borrowed"
+    let mut a = List::<i32>::new();
+    let mut b = List::new();
+    let handle = b.push_back(1);
+    let _guard = b.peek_front_mut().unwrap();
+    a.remove_node(&handle);
+  }
+
+  #[test]
+  fn middle_handle_from_another_list() {
+    // a middle node has neighbours on both sides, so only the owner id can give it away
+    let mut a = List::from(vec![0]);
+    let mut b = List::new();
+    b.push_back(1);
+    let two = b.push_back(2);
+    b.push_back(3);
+
+    for attempt in 0..3 {
+      let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match attempt {
+        0 => { a.remove_node(&two); }
+        1 => { a.move_to_front(&two); }
+        _ => { a.move_to_back(&two); }
+      }));
+      assert!(result.is_err());
+    }
+    // neither list noticed a thing
+    assert_eq!(a, List::from(vec![0]));
+    assert_eq!(b, List::from(vec![1, 2, 3]));
+    assert_eq!(b.remove_node(&two), Some(2));
+  }
+
+  #[test]
+  fn handles_follow_their_nodes() {
+    // append retags whichever side is shorter, so try it both ways round
+    let mut a = List::new();
+    let zero = a.push_back(0);
+    let mut b = List::new();
+    let handles: Vec<_> = (1..5).map(|i| b.push_back(i)).collect();
+    a.append(&mut b);
+    assert_eq!(a.remove_node(&zero), Some(0));
+    assert_eq!(a.remove_node(&handles[3]), Some(4));
+
+    let mut short = List::from(vec![8]);
+    let nine = short.push_back(9);
+    short.append(&mut a);
+    assert_eq!(short.remove_node(&nine), Some(9));
+    assert_eq!(short.remove_node(&handles[0]), Some(1));
+    assert_eq!(short, List::from(vec![8, 2, 3]));
+
+    // split_off too, with the short half at either end
+    let mut back = short.split_off(1);
+    assert!(back.move_to_front(&handles[2]));
+    assert_eq!(back, List::from(vec![3, 2]));
+    let four = back.push_back(4);
+    let mut last = back.split_off(2);
+    assert_eq!(last.remove_node(&four), Some(4));
+    assert_eq!(back.remove_node(&handles[1]), Some(2));
+
+    // and partition and splice_at
+    let (mut odd, _even) = List::from(vec![5, 7, 8]).partition(|x| x % 2 == 1);
+    let eleven = odd.push_back(11);
+    let mut spliced = List::from(vec![0, 0]);
+    spliced.splice_at(1, odd);
+    assert_eq!(spliced.remove_node(&eleven), Some(11));
+    assert_eq!(spliced, List::from(vec![0, 5, 7, 0]));
+  }
+
+  #[test]
+  fn swap() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);
+    let last = list.push_back(5);
+    list.swap(0, 5);
+    list.swap(4, 1);
+    list.swap(3, 3);
+    assert_eq!(list, List::from(vec![5, 4, 2, 3, 1, 0]));
+    assert_eq!(*list.peek_front().unwrap(), 5);
+    assert_eq!(*list.peek_back().unwrap(), 0);
+
+    // the handle stayed with its node at the back
+    assert_eq!(list.remove_node(&last), Some(0));
+  }
+
+  #[test]
+  #[should_panic(expected = "swap indices (0, 2) out of bounds for a list of length 2")]
+  fn swap_past_end() {
+    let mut list = List::from(vec![0, 1]);
+    list.swap(0, 2);
+  }
+
+  #[test]
+  fn rotate() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);
+    list.rotate_left(2);
+    assert_eq!(list, List::from(vec![2, 3, 4, 0, 1]));
+    list.rotate_right(3);
+    assert_eq!(list, List::from(vec![4, 0, 1, 2, 3]));
+    list.rotate_left(0);
+    list.rotate_right(5);
+    assert_eq!(list, List::from(vec![4, 0, 1, 2, 3]));
+    assert_eq!(list.len(), 5);
+    assert_eq!(*list.peek_back().unwrap(), 3);
+
+    // round-robin: the one at the front gets a turn, then goes to the back of the line
+    let mut queue = List::from(vec!['a', 'b', 'c']);
+    let mut turns = String::new();
+    for _ in 0..5 {
+      turns.push(*queue.peek_front().unwrap());
+      queue.rotate_left(1);
+    }
+    assert_eq!(turns, "abcab");
+
+    let mut empty: List<i32> = List::new();
+    empty.rotate_left(0);
+    empty.rotate_right(0);
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  fn rotate_keeps_handles() {
+    let mut list = List::new();
+    let first = list.push_back(0);
+    list.push_back(1);
+    list.push_back(2);
+
+    // the nodes themselves went round, so handles still work
+    list.rotate_left(1);
+    assert!(list.move_to_front(&first));
+    assert_eq!(list, List::from(vec![0, 1, 2]));
+  }
+
+  #[test]
+  #[should_panic(expected = "rotation by 4 out of bounds for a list of length 3")]
+  fn rotate_past_end() {
+    let mut list = List::from(vec![0, 1, 2]);
+    list.rotate_right(4);
+  }
+
+  #[test]
+  fn dedup() {
+    let mut list = List::from(vec![1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    list.dedup();
+    assert_eq!(list, List::from(vec![1, 2, 3, 1, 4]));
+    assert_eq!(list.len(), 5);
+    assert_eq!(*list.peek_back().unwrap(), 4);
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.pop_back(), Some(1));
+
+    let mut list = List::from(vec![7, 7, 7]);
+    list.dedup();
+    assert_eq!(list.into_vec(), vec![7]);
+
+    let mut list: List<i32> = List::new();
+    list.dedup();
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn dedup_by() {
+    // same_bucket sees the current element first, then the last one kept, and can fold one into the other
+    let mut list = List::from(vec![("a", 1), ("a", 2), ("b", 3), ("a", 4), ("a", 5)]);
+    list.dedup_by(|cur, kept| {
+      let same = cur.0 == kept.0;
+      if same {
+        kept.1 += cur.1;
+      }
+      same
+    });
+    assert_eq!(list.into_vec(), vec![("a", 3), ("b", 3), ("a", 9)]);
+
+    let drops = Rc::new(Cell::new(0));
+    let mut list = List::new();
+    for _ in 0..5 {
+      list.push_back(Tracked(drops.clone()));
+    }
+    list.dedup_by(|_, _| true);
+    assert_eq!(drops.get(), 4);
+    assert_eq!(list.len(), 1);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde() {
+    let list = List::from(vec![1, 2, 3]);
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, "[1,2,3]");
+
+    let mut back: List<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, list);
+    // the links came back in both directions
+    assert_eq!(back.pop_back(), Some(3));
+    assert_eq!(*back.peek_back().unwrap(), 2);
+
+    assert_eq!(serde_json::to_string(&List::<i32>::new()).unwrap(), "[]");
+    assert!(serde_json::from_str::<List<i32>>("{}").is_err());
+  }
+
+  #[test]
+  fn get() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);
+    assert_eq!(*list.get(0).unwrap(), 0);
+    assert_eq!(*list.get(4).unwrap(), 4);
+    assert!(list.get(5).is_none());
+
+    // guards on different nodes, from both halves, side by side
+    let (one, three) = (list.get(1).unwrap(), list.get(3).unwrap());
+    assert_eq!((*one, *three), (1, 3));
+    drop((one, three));
+
+    *list.get_mut(2).unwrap() = 20;
+    assert!(list.get_mut(5).is_none());
+    assert_eq!(list.get_cloned(2), Some(20));
+    assert_eq!(list.get_cloned(9), None);
+
+    let empty: List<i32> = List::new();
+    assert!(empty.get(0).is_none());
+  }
+
+  #[test]
+  fn iter_cloned() {
+    let mut list = List::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+    assert_eq!(list.iter_cloned().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(list.iter_cloned().rev().collect::<Vec<_>>(), vec!["c", "b", "a"]);
+
+    // the two ends meet in the middle without handing anything out twice
+    let mut iter = list.iter_cloned();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back().as_deref(), Some("c"));
+    assert_eq!(iter.next().as_deref(), Some("a"));
+    assert_eq!(iter.next_back().as_deref(), Some("b"));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    drop(iter);
+
+    // the iterator's done with the nodes, so popping is fine again
+    assert_eq!(list.pop_front().as_deref(), Some("a"));
+    assert_eq!(List::<i32>::new().iter_cloned().next(), None);
+  }
+
+  // One workout for every kind of cell. The algorithm's the same code either way, so this is mostly checking the
+  // plumbing underneath it.
+  fn workout<K: Kind>() {
+    let mut list: List<i32, K> = (1..=5).collect();
+    let three = list.push_back(6);
+    list.push_front(0);
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5, 6]");
+    assert_eq!((*list.peek_front().unwrap(), *list.peek_back().unwrap()), (0, 6));
+
+    *list.get_mut(3).unwrap() *= 10;
+    assert_eq!(*list.find(|&x| x > 20).unwrap(), 30);
+    list.retain(|&x| x % 2 == 0);
+    assert!(list.move_to_front(&three));
+    list.reverse();
+    assert_eq!(list.iter_cloned().collect::<Vec<_>>(), vec![4, 30, 2, 0, 6]);
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(30));
+    *cursor.current().unwrap() += 1;
+    drop(cursor);
+    assert_eq!(list.clone(), list);
+    assert_eq!(list.split_off(2).into_iter().rev().collect::<Vec<_>>(), vec![6, 0]);
+    assert_eq!(list.drain().collect::<Vec<_>>(), vec![4, 3]);
+
+    let drops = Rc::new(Cell::new(0));
+    let mut list: List<_, K> = List::with_cell();
+    for _ in 0..4 {
+      list.push_back(Tracked(drops.clone()));
+    }
+    list.pop_back();
+    list.remove(1);
+    drop(list);
+    assert_eq!(drops.get(), 4);
+  }
+
+  #[test]
+  fn kinds() {
+    workout::<Checked>();
+    workout::<Locked>();
+  }
+
+  fn foreign_handle_under_a_guard<K: Kind>() {
+    // Turning a's handle down mustn't go near b's node while b has it lent out. Run this under Miri, in release
+    // too: a kind without runtime checks would read straight through g here.
+    let mut a: List<i32, K> = List::with_cell();
+    let mut b: List<i32, K> = List::with_cell();
+    a.push_back(0);
+    let h_b = b.push_back(1);
+    let mut g = b.peek_front_mut().unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| a.remove_node(&h_b)));
+    assert!(result.is_err());
+    *g += 1;
+    drop(g);
+    assert_eq!(b.into_vec(), vec![2]);
+  }
+
+  #[test]
+  fn foreign_handles_leave_guards_alone() {
+    foreign_handle_under_a_guard::<Checked>();
+    foreign_handle_under_a_guard::<Locked>();
+  }
+
+  #[test]
+  fn locked_across_threads() {
+    fn is_send_sync<T: Send + Sync>() {}
+    is_send_sync::<List<i32, Locked>>();
+
+    // build it here, fill it up over there, and then read it from two threads at once
+    let list: List<i32, Locked> = List::with_cell();
+    let list = std::thread::spawn(move || {
+      let mut list = list;
+      list.extend(0..100);
+      list
+    }).join().unwrap();
+
+    let list = std::sync::Arc::new(list);
+    let readers: Vec<_> = (0..2).map(|_| {
+      let list = list.clone();
+      std::thread::spawn(move || {
+        let mut sum = 0;
+        list.for_each(|x| sum += x);
+        (sum, list.contains(&99))
+      })
+    }).collect();
+    for reader in readers {
+      assert_eq!(reader.join().unwrap(), (4950, true));
+    }
+  }
+
+  #[test]
+  fn display() {
+    let mut list = List::new();
+    assert_eq!(list.to_string(), "");
+    assert_eq!(list.separated_by(", ").to_string(), "");
+
+    list.push_back("first");
+    assert_eq!(list.separated_by(", ").to_string(), "first");
+
+    list.push_back("second");
+    list.push_back("third");
+    assert_eq!(list.to_string(), "first second third");
+    assert_eq!(list.separated_by(", ").to_string(), "first, second, third");
+    assert_eq!(format!("[{}]", list.separated_by("] [")), "[first] [second] [third]");
+
+    // the element's own formatting flags get passed along
+    let list = List::from(vec![1.0, 2.5]);
+    assert_eq!(format!("{:.2}", list), "1.00 2.50");
+  }
+
+  #[test]
+  fn std_conversions() {
+    use std::collections::{LinkedList, VecDeque};
+
+    let std_list: LinkedList<_> = (0..4).collect();
+    let list = List::from(std_list);
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3]");
+    let std_list: LinkedList<_> = list.into();
+    assert_eq!(std_list.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+    let mut deque = VecDeque::new();
+    deque.push_back(2);
+    deque.push_front(1);
+    let mut list = List::from(deque);
+    assert_eq!(*list.peek_back().unwrap(), 2);
+    list.push_back(3);
+    let deque: VecDeque<_> = list.into();
+    assert_eq!(deque, vec![1, 2, 3]);
+
+    // and the other kinds of cell can go out the same way
+    let locked: List<_, Locked> = (0..2).collect();
+    assert_eq!(VecDeque::from(locked), vec![0, 1]);
+    assert!(LinkedList::from(List::<i32>::new()).is_empty());
+  }
+
+  #[test]
+  fn partition() {
+    let mut list = List::new();
+    let handles: Vec<_> = (0..7).map(|i| list.push_back(i)).collect();
+
+    let (mut evens, mut odds) = list.partition(|x| x % 2 == 0);
+    assert_eq!(format!("{:?} {:?}", evens, odds), "[0, 2, 4, 6] [1, 3, 5]");
+    assert_eq!((evens.len(), odds.len()), (4, 3));
+    assert_eq!(*odds.peek_back().unwrap(), 5);
+
+    // the handles went with their nodes
+    assert!(odds.move_to_front(&handles[5]));
+    assert_eq!(evens.remove_node(&handles[6]), Some(6));
+    assert_eq!(odds.into_vec(), vec![5, 1, 3]);
+    assert_eq!(evens.into_iter().rev().collect::<Vec<_>>(), vec![4, 2, 0]);
+
+    let (all, none) = List::from(vec![1, 2]).partition(|_| true);
+    assert_eq!((all.len(), none.len()), (2, 0));
+    let (yes, no) = List::<i32>::new().partition(|_| true);
+    assert!(yes.is_empty() && no.is_empty());
+  }
+
+  #[test]
+  fn for_each_mut() {
+    let mut list = List::new();
+    list.for_each_mut(|_: &mut i32| panic!("empty list has nothing to visit"));
+
+    list.extend(vec![1, 2, 3]);
+    list.for_each_mut(|x| *x *= 10);
+    assert_eq!(format!("{:?}", list), "[10, 20, 30]");
+
+    // state carried between calls, and the links are untouched
+    let mut total = 0;
+    list.for_each_mut(|x| {
+      total += *x;
+      *x = total;
+    });
+    assert_eq!(list.pop_back(), Some(60));
+    assert_eq!(list.pop_back(), Some(30));
+    assert_eq!(list.pop_back(), Some(10));
+  }
+
+  #[test]
+  fn long_drop() {
+    // a recursive drop of the node chain would blow the stack long before this
+    let mut list = List::new();
+    for i in 0..200_000 {
+      list.push_back(i);
+    }
+    drop(list);
+  }
+
+  // Counts its own drops, so a leaked node shows up as a drop that never happened.
+  struct Tracked(Rc<Cell<usize>>);
+
+  impl Drop for Tracked {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+
+  #[test]
+  fn no_leaks() {
+    let drops = Rc::new(Cell::new(0));
+
+    let mut list = List::new();
+    for _ in 0..5 {
+      list.push_back(Tracked(drops.clone()));
+      list.push_front(Tracked(drops.clone()));
+    }
+    drop(list);
+    assert_eq!(drops.get(), 10);
+
+    // popping from either end hands the element over without leaving its node behind
+    drops.set(0);
+    let mut list = List::new();
+    for _ in 0..4 {
+      list.push_back(Tracked(drops.clone()));
+    }
+    drop(list.pop_front());
+    drop(list.pop_back());
+    assert_eq!(drops.get(), 2);
+    drop(list);
+    assert_eq!(drops.get(), 4);
+  }
+
+  #[test]
+  fn no_strong_back_links() {
+    let mut list = List::new();
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+
+    // head is owned by the list alone, the middle by its predecessor, and the tail by both its predecessor and
+    // the list's tail pointer. A strong prev would add one more to every node but the head.
+    let head = list.head.as_ref().unwrap();
+    let mid = head.borrow().next.clone().unwrap();
+    let tail = list.tail.as_ref().unwrap();
+    assert_eq!(Rc::strong_count(head), 1);
+    assert_eq!(Rc::strong_count(&mid), 2); // plus our clone
+    assert_eq!(Rc::strong_count(tail), 2);
+    assert_eq!(Rc::weak_count(head), 1);
+    assert_eq!(Rc::weak_count(tail), 0);
+  }
+
+  #[test]
+  fn into_iter() {
+    let mut list = List::new();
+
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
 
     let mut iter = list.into_iter();
     assert_eq!(iter.next(), Some(3));
@@ -243,4 +2719,52 @@ mod test {
     assert_eq!(iter.next_back(), None);
     assert_eq!(iter.next(), None);
   }
+
+  #[test]
+  fn into_iter_rev() {
+    let mut list = List::new();
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+
+    let mut seen = Vec::new();
+    for elem in list {
+      seen.push(elem);
+    }
+    assert_eq!(seen, vec![1, 2, 3]);
+
+    let mut list = List::new();
+    list.push_back(1);
+    list.push_back(2);
+    list.push_back(3);
+    assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+  }
+
+  // These two leak on purpose, which Miri won't have.
+  #[test]
+  #[cfg(feature = "borrow-audit")]
+  #[cfg_attr(miri, ignore)]
+  #[should_panic(expected = "List::pop_front couldn't borrow a node mutably: List::peek_front borrowed it last")]
+  fn borrow_audit_names_the_holder() {
+    // the list is never dropped, since its drop would hit the same clash mid-unwind and abort the test binary
+    let mut list = std::mem::ManuallyDrop::new(List::new());
+    list.push_back(1);
+    list.push_back(2);
+    std::mem::forget(list.peek_front());
+    list.pop_front();
+  }
+
+  #[test]
+  #[cfg(feature = "borrow-audit")]
+  #[cfg_attr(miri, ignore)]
+  #[should_panic(expected = "List::pop_front couldn't borrow a node mutably: List::get borrowed it last")]
+  fn borrow_audit_only_knows_the_last_borrow() {
+    // peek_front's guard is the one in the way, but get came along after it and that's the name that sticks
+    let mut list = std::mem::ManuallyDrop::new(List::new());
+    list.push_back(1);
+    list.push_back(2);
+    std::mem::forget(list.peek_front());
+    assert_eq!(*list.get(0).unwrap(), 1);
+    list.pop_front();
+  }
 }
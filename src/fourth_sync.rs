@@ -0,0 +1,176 @@
+// fourth, but for threads. fourth::List<T, Locked> already swaps Rc for Arc and RefCell for RwLock, so it can go
+// to another thread just fine. What it can't do is be pushed to from two threads at once, since every push and pop
+// takes &mut self and has to touch the list's ends as well as a node.
+//
+// So this is just that list behind a Mutex, with everything going through &self. One lock for the whole list means
+// there's no lock order to get wrong and no deadlocks. Share one with an Arc and push and pop from wherever.
+//
+// The catch: the Mutex guard only lives as long as the call, so there's no Ref-style peek. We clone the element out
+// instead. If you want the real thing, lock it yourself: a Mutex<fourth::List<T, Locked>> is all this is.
+
+use crate::fourth::{self, Locked};
+use std::sync::{Mutex, MutexGuard}; // much thread safe, again
+
+pub struct List<T> {
+  list: Mutex<fourth::List<T, Locked>>,
+}
+
+impl<T> List<T> {
+  pub fn new() -> Self {
+    List { list: Mutex::new(fourth::List::with_cell()) }
+  }
+
+  // A thread that panicked while holding the lock leaves it poisoned. Nothing we do under it can panic halfway
+  // through relinking, so whatever's behind it is still in one piece and we carry on.
+  fn lock(&self) -> MutexGuard<'_, fourth::List<T, Locked>> {
+    self.list.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  pub fn len(&self) -> usize {
+    self.lock().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn push_front(&self, elem: T) {
+    self.lock().push_front(elem);
+  }
+
+  pub fn push_back(&self, elem: T) {
+    self.lock().push_back(elem);
+  }
+
+  pub fn pop_front(&self) -> Option<T> {
+    self.lock().pop_front()
+  }
+
+  pub fn pop_back(&self) -> Option<T> {
+    self.lock().pop_back()
+  }
+
+  pub fn peek_front(&self) -> Option<T> where T: Clone {
+    let list = self.lock();
+    let elem = list.peek_front().map(|elem| elem.clone());
+    elem
+  }
+
+  pub fn peek_back(&self) -> Option<T> where T: Clone {
+    let list = self.lock();
+    let elem = list.peek_back().map(|elem| elem.clone());
+    elem
+  }
+
+  pub fn into_inner(self) -> fourth::List<T, Locked> {
+    self.list.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+impl<T> Default for List<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> From<fourth::List<T, Locked>> for List<T> {
+  fn from(list: fourth::List<T, Locked>) -> Self {
+    List { list: Mutex::new(list) }
+  }
+}
+
+impl<T> IntoIterator for List<T> {
+  type Item = T;
+  type IntoIter = fourth::IntoIter<T, Locked>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.into_inner().into_iter()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::List;
+  use std::sync::Arc;
+  use std::thread;
+
+  #[test]
+  fn basics() {
+    let list = List::new();
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.pop_back(), None);
+
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.peek_front(), Some(1));
+    assert_eq!(list.peek_back(), Some(3));
+
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+    assert_eq!(list.peek_front(), None);
+
+    list.push_front(4);
+    list.push_front(5);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![5, 4]);
+  }
+
+  #[test]
+  fn send_sync() {
+    fn is_send_sync<T: Send + Sync>() {}
+    is_send_sync::<List<i32>>();
+  }
+
+  #[test]
+  fn two_pushers() {
+    let list = Arc::new(List::new());
+
+    let front = {
+      let list = list.clone();
+      thread::spawn(move || for i in 0..1000 { list.push_front(-i - 1) })
+    };
+    let back = {
+      let list = list.clone();
+      thread::spawn(move || for i in 0..1000 { list.push_back(i) })
+    };
+    front.join().unwrap();
+    back.join().unwrap();
+
+    // however the pushes interleaved, each side only ever grew outwards
+    let list = Arc::try_unwrap(list).ok().unwrap().into_inner();
+    assert_eq!(list.len(), 2000);
+    assert_eq!(list.into_vec(), (-1000..1000).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn pusher_and_popper() {
+    let list = Arc::new(List::new());
+
+    let pusher = {
+      let list = list.clone();
+      thread::spawn(move || for i in 0..1000 { list.push_back(i) })
+    };
+    let popper = {
+      let list = list.clone();
+      thread::spawn(move || {
+        let mut got = Vec::new();
+        while got.len() < 1000 {
+          match list.pop_front() {
+            Some(x) => got.push(x),
+            None => thread::yield_now(),
+          }
+        }
+        got
+      })
+    };
+
+    pusher.join().unwrap();
+    // a queue across threads still comes out in the order it went in
+    assert_eq!(popper.join().unwrap(), (0..1000).collect::<Vec<_>>());
+    assert!(list.is_empty());
+  }
+}
@@ -3,5 +3,9 @@ pub mod second;
 pub mod third;
 pub mod third_with_arc;
 pub mod fourth;
+pub mod fourth_sync;
 pub mod fifth;
 pub mod silly1;
+pub mod text_buffer;
+pub mod edit_log;
+pub mod bounded_list;
@@ -16,7 +16,7 @@ impl<T> List<T> {
 
   pub fn push(&mut self, elem: T) {
       let new_node = Box::new(Node {
-          elem: elem,
+          elem,
           next: self.head.take(),
       });
 
@@ -42,10 +42,6 @@ impl<T> List<T> {
       })
   }
 
-  pub fn into_iter(self) -> IntoIter<T> {
-      IntoIter(self)
-  }
-
   pub fn iter(&self) -> Iter<'_, T> {
       Iter { next: self.head.as_deref() }
   }
@@ -55,6 +51,12 @@ impl<T> List<T> {
   }
 }
 
+impl<T> Default for List<T> {
+  fn default() -> Self {
+      Self::new()
+  }
+}
+
 impl<T> Drop for List<T> {
   fn drop(&mut self) {
       let mut cur_link = self.head.take();
@@ -66,6 +68,15 @@ impl<T> Drop for List<T> {
 
 pub struct IntoIter<T>(List<T>);
 
+impl<T> IntoIterator for List<T> {
+  type Item = T;
+  type IntoIter = IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+      IntoIter(self)
+  }
+}
+
 impl<T> Iterator for IntoIter<T> {
   type Item = T;
   fn next(&mut self) -> Option<Self::Item> {
@@ -146,9 +157,9 @@ mod test {
       assert_eq!(list.peek(), Some(&3));
       assert_eq!(list.peek_mut(), Some(&mut 3));
 
-      list.peek_mut().map(|value| {
+      if let Some(value) = list.peek_mut() {
           *value = 42
-      });
+      }
 
       assert_eq!(list.peek(), Some(&42));
       assert_eq!(list.pop(), Some(42));
@@ -16,14 +16,54 @@
 // never go back up the list, while our finger can!
 
 
-pub struct List<T> {
-  left: Stack<T>,
-  right: Stack<T>,
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::{FromIterator, Rev};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+// The two halves don't have to be our linked Stack, anything that can act like a stack will do. The linked one can
+// move nodes across the finger without reallocating, a Vec is kinder to the cache. Like HashMap's hasher, the backend
+// is a defaulted type parameter, so plain List<T> is the linked version and new() only builds that one.
+#[derive(Clone)]
+pub struct List<T, S = Stack<T>> {
+  left: S,
+  right: S,
+  marker: PhantomData<T>,
 }
 
 impl<T> List<T> {
   pub fn new() -> Self {
-      List { left: Stack::new(), right: Stack::new() }
+      List::with_backend()
+  }
+
+  // cursor is how many elements end up left of the finger (clamped to the length), None puts it at the front
+  pub fn from_vec(vec: Vec<T>, cursor: Option<usize>) -> Self {
+    let mut list = List::new();
+    for elem in vec.into_iter().rev() {
+      list.push_right(elem);
+    }
+    list.seek(cursor.unwrap_or(0).min(list.len()));
+    list
+  }
+
+}
+
+impl<T, S: StackBackend<T>> List<T, S> {
+  pub fn with_backend() -> Self {
+    List { left: S::default(), right: S::default(), marker: PhantomData }
+  }
+
+  // the old stacks get dropped, and Stack's Drop already pops its nodes one at a time
+  pub fn clear(&mut self) {
+    self.left = S::default();
+    self.right = S::default();
+  }
+
+  pub fn into_vec(self) -> Vec<T> {
+    self.into_iter().collect()
   }
 
   pub fn push_left(&mut self, elem: T) {
@@ -58,21 +98,778 @@ impl<T> List<T> {
     self.right.peek_mut()
   }
 
+  // overwrite the element next to the finger and hand back what was there. like HashMap::insert, None means
+  // there was nothing there, and elem just gets pushed on that side instead
+  pub fn replace_left(&mut self, elem: T) -> Option<T> {
+    match self.left.peek_mut() {
+      Some(old) => Some(mem::replace(old, elem)),
+      None => {
+        self.left.push(elem);
+        None
+      }
+    }
+  }
+
+  pub fn replace_right(&mut self, elem: T) -> Option<T> {
+    match self.right.peek_mut() {
+      Some(old) => Some(mem::replace(old, elem)),
+      None => {
+        self.right.push(elem);
+        None
+      }
+    }
+  }
+
+  // by absolute position. the finger splits the list, so whichever side index is on, we walk that stack down from
+  // the finger: the cost is the distance from the finger, not from the front
+  pub fn get(&self, index: usize) -> Option<&T> {
+    let position = self.position();
+    if index < position {
+      self.left.top_down().nth(position - 1 - index)
+    } else {
+      self.right.top_down().nth(index - position)
+    }
+  }
+
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    let position = self.position();
+    if index < position {
+      self.left.top_down_mut().nth(position - 1 - index)
+    } else {
+      self.right.top_down_mut().nth(index - position)
+    }
+  }
+
+  // go_left/go_right but treating the list as a ring: stepping off one end brings the finger round to the other.
+  // wrapping has to move the whole list across, so it's O(n), but only once per lap. false if the list is empty
+  pub fn rotate_left(&mut self) -> bool {
+    if self.left.is_empty() {
+      self.seek_back();
+    }
+    self.go_left()
+  }
+
+  pub fn rotate_right(&mut self) -> bool {
+    if self.right.is_empty() {
+      self.seek_front();
+    }
+    self.go_right()
+  }
+
+  // pop one side, nearest the finger first. it's lazy: only what actually gets yielded is removed, so
+  // drain_left().take(n) eats n elements and leaves the rest alone
+  pub fn drain_left(&mut self) -> Drain<'_, T, S> {
+    Drain { stack: &mut self.left, marker: PhantomData }
+  }
+
+  pub fn drain_right(&mut self) -> Drain<'_, T, S> {
+    Drain { stack: &mut self.right, marker: PhantomData }
+  }
+
+  // scan outward from the finger: iter_left goes right to left ending at the front, iter_right goes left to right
+  // ending at the back. both are just the stack walked from the top
+  pub fn iter_left(&self) -> S::Iter<'_> {
+    self.left.top_down()
+  }
+
+  pub fn iter_right(&self) -> S::Iter<'_> {
+    self.right.top_down()
+  }
+
+  // run f over up to k elements on each side of the finger, in list order, walking down the stacks with &mut
+  // rather than moving the finger around
+  pub fn map_around<F>(&mut self, k: usize, mut f: F) where F: FnMut(&mut T) {
+    let mut before: Vec<_> = self.left.top_down_mut().take(k).collect();
+    before.reverse();
+    before.into_iter().for_each(&mut f);
+    self.right.top_down_mut().take(k).for_each(f);
+  }
+
+  // lookahead without moving the finger, 0 is the same as peek_left/peek_right
+  pub fn peek_nth_left(&self, n: usize) -> Option<&T> {
+    self.left.top_down().nth(n)
+  }
+
+  pub fn peek_nth_right(&self, n: usize) -> Option<&T> {
+    self.right.top_down().nth(n)
+  }
+
+  // up to k elements either side of the finger, both in list order so they can be printed as is
+  pub fn context(&self, k: usize) -> (Vec<&T>, Vec<&T>) {
+    let mut before: Vec<_> = self.left.top_down().take(k).collect();
+    before.reverse();
+    (before, self.right.top_down().take(k).collect())
+  }
+
   pub fn go_left(&mut self) -> bool {
-    self.left.pop_node().map(|node| {
-      self.right.push_node(node);
-    }).is_some()
+    self.left.shift_to(&mut self.right)
   }
 
   pub fn go_right(&mut self) -> bool {
-    self.right.pop_node().map(|node| {
-      self.left.push_node(node);
+    self.right.shift_to(&mut self.left)
+  }
+
+  // drop all of other in at the finger, leaving the finger after it. we move other's finger to its far left so
+  // its right stack is the whole thing in order, then peel it off onto our left stack. with the linked backend
+  // that's just relinking nodes, no allocations
+  pub fn splice(&mut self, mut other: List<T, S>) {
+    while other.go_left() {}
+    while other.right.shift_to(&mut self.left) {}
+  }
+
+  // each stack read from the top is the list walking away from the finger, so trading them flips the whole list.
+  // the finger stays between the same two elements, which puts it at len - position
+  pub fn reverse(&mut self) {
+    mem::swap(&mut self.left, &mut self.right);
+  }
+
+  // trade the elements on either side of the finger, false if one side is empty
+  pub fn swap_across(&mut self) -> bool {
+    match (self.left.peek_mut(), self.right.peek_mut()) {
+      (Some(left), Some(right)) => {
+        mem::swap(left, right);
+        true
+      }
+      _ => false,
+    }
+  }
+
+  // these all return how far the finger actually got, which is less than n if we ran into an end
+  pub fn go_left_n(&mut self, n: usize) -> usize {
+    let mut moved = 0;
+    while moved < n && self.go_left() {
+      moved += 1;
+    }
+    moved
+  }
+
+  pub fn go_right_n(&mut self, n: usize) -> usize {
+    let mut moved = 0;
+    while moved < n && self.go_right() {
+      moved += 1;
+    }
+    moved
+  }
+
+  pub fn seek_front(&mut self) -> usize {
+    self.go_left_n(usize::MAX)
+  }
+
+  pub fn seek_back(&mut self) -> usize {
+    self.go_right_n(usize::MAX)
+  }
+
+  // pred sees the elements in order, left to right. the finger ends up between the survivors that were left of it
+  // and the survivors that were right of it
+  pub fn retain<F>(&mut self, mut pred: F) where F: FnMut(&T) -> bool {
+    let position = self.seek_front();
+    let mut seen = 0;
+    let mut kept_left = 0;
+    self.right.retain(|elem| {
+      let keep = pred(elem);
+      if keep && seen < position {
+        kept_left += 1;
+      }
+      seen += 1;
+      keep
+    });
+    self.go_right_n(kept_left);
+  }
+
+  // tack other on at the back (or front), leaving our finger where it is. other gets its finger moved to the
+  // matching end, which lines its nodes up as a stack we can hang underneath ours: the linked backend walks to
+  // the bottom of our stack and relinks once, nothing gets reallocated. that's not O(1) though, and can't be:
+  // seeking other moves everything on its far side of the finger across one element at a time, which is just
+  // what a finger list does, and finding our bottom walks our whole right stack (left, for prepend). so it's
+  // O(our side + other's far side). a bottom pointer would only save the second walk, and a raw pointer into a
+  // Box chain is the same aliasing mess fifth had to get rid of. the Vec backend copies our side instead
+  pub fn append(&mut self, mut other: List<T, S>) {
+    other.seek_front();
+    self.right.append_below(other.right);
+  }
+
+  pub fn prepend(&mut self, mut other: List<T, S>) {
+    other.seek_back();
+    self.left.append_below(other.left);
+  }
+
+  // scan for a match, stopping with it as the current element (just right of the finger) either way, so
+  // find_forward(..) then move_current_to_front() does what it says. no match leaves the finger at the end it
+  // ran into
+  pub fn find_forward<P>(&mut self, mut pred: P) -> bool where P: FnMut(&T) -> bool {
+    loop {
+      match self.right.peek() {
+        Some(elem) if pred(elem) => return true,
+        Some(_) => self.go_right(),
+        None => return false,
+      };
+    }
+  }
+
+  pub fn find_backward<P>(&mut self, mut pred: P) -> bool where P: FnMut(&T) -> bool {
+    loop {
+      match self.left.peek() {
+        Some(elem) if pred(elem) => return self.go_left(),
+        Some(_) => self.go_left(),
+        None => return false,
+      };
+    }
+  }
+
+  // the current element is the one just right of the finger. these pull it out and put it at the very front or
+  // back, the finger stays between the same neighbours it had. false if there's nothing right of the finger
+  pub fn move_current_to_front(&mut self) -> bool {
+    match self.right.pop() {
+      Some(elem) => {
+        let mut front = S::default();
+        front.push(elem);
+        self.left.append_below(front);
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn move_current_to_back(&mut self) -> bool {
+    match self.right.pop() {
+      Some(elem) => {
+        let mut back = S::default();
+        back.push(elem);
+        self.right.append_below(back);
+        true
+      }
+      None => false,
+    }
+  }
+
+  // everything right of the finger becomes its own list, with its finger at the front. the right stack is already
+  // that list's right stack, so we just hand it over
+  pub fn split_off(&mut self) -> List<T, S> {
+    List { left: S::default(), right: mem::take(&mut self.right), marker: PhantomData }
+  }
+
+  // take out the element n steps away from the finger (0 is the one right next to it) without moving the finger.
+  // the linked backend just walks an &mut down the stack's links to get there
+  pub fn remove_left_nth(&mut self, n: usize) -> Option<T> {
+    self.left.remove_nth(n)
+  }
+
+  pub fn remove_right_nth(&mut self, n: usize) -> Option<T> {
+    self.right.remove_nth(n)
+  }
+
+  // where the finger is, counting from 0 at the far left. same as left_len, but reads better next to seek
+  pub fn position(&self) -> usize {
+    self.left.len()
+  }
+
+  // put the finger so that index elements are to its left. false (and the finger stays put) if the list
+  // isn't that long
+  pub fn seek(&mut self, index: usize) -> bool {
+    if index > self.len() {
+      return false;
+    }
+    let position = self.position();
+    if index < position {
+      self.go_left_n(position - index);
+    } else {
+      self.go_right_n(index - position);
+    }
+    true
+  }
+
+  // remember where the finger is so it can be put back after wandering off. the token is just the index, so edits
+  // left of the finger in the meantime shift what it points at
+  pub fn save_cursor(&self) -> CursorToken {
+    CursorToken(self.position())
+  }
+
+  // false if the list has got too short to go back there, in which case the finger stays put
+  pub fn restore_cursor(&mut self, token: CursorToken) -> bool {
+    self.seek(token.0)
+  }
+
+  // every push, pop and go goes through the stacks, which keep their own counts
+  pub fn len(&self) -> usize {
+    self.left.len() + self.right.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.left.is_empty() && self.right.is_empty()
+  }
+
+  pub fn left_len(&self) -> usize {
+    self.left.len()
+  }
+
+  pub fn right_len(&self) -> usize {
+    self.right.len()
+  }
+
+  // == only cares about the elements, this also wants the finger in the same spot. comparing the stacks
+  // directly gets us both at once without iter having to buffer anything
+  pub fn eq_with_cursor(&self, other: &List<T, S>) -> bool where T: PartialEq {
+    self.left.top_down().eq(other.left.top_down()) && self.right.top_down().eq(other.right.top_down())
+  }
+
+  // the whole list left to right, wherever the finger is. see Half for how each side gets walked
+  pub fn iter(&self) -> Iter<'_, T, S> {
+    Iter {
+      left: Half::new(self.left.top_down(), self.left.len()),
+      right: Half::new(self.right.top_down(), self.right.len()),
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> IterMut<'_, T, S> {
+    let (left_len, right_len) = (self.left.len(), self.right.len());
+    IterMut {
+      left: Half::new(self.left.top_down_mut(), left_len),
+      right: Half::new(self.right.top_down_mut(), right_len),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorToken(usize);
+
+impl CursorToken {
+  pub fn position(&self) -> usize {
+    self.0
+  }
+}
+
+impl<T, S: StackBackend<T>> Default for List<T, S> {
+  fn default() -> Self {
+    List::with_backend()
+  }
+}
+
+impl<T> From<Vec<T>> for List<T> {
+  fn from(vec: Vec<T>) -> Self {
+    List::from_vec(vec, None)
+  }
+}
+
+impl<T, S: StackBackend<T>> From<List<T, S>> for Vec<T> {
+  fn from(list: List<T, S>) -> Self {
+    list.into_vec()
+  }
+}
+
+// the new elements go right after the finger in the order they came in, so they have to be pushed backwards
+impl<T, S: StackBackend<T>> Extend<T> for List<T, S> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let elems: Vec<_> = iter.into_iter().collect();
+    for elem in elems.into_iter().rev() {
+      self.push_right(elem);
+    }
+  }
+}
+
+// everything ends up right of the finger, with the finger at the front
+impl<T, S: StackBackend<T>> FromIterator<T> for List<T, S> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut list = List::with_backend();
+    list.extend(iter);
+    list
+  }
+}
+
+impl<T: PartialEq, S: StackBackend<T>> PartialEq for List<T, S> {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.iter().eq(other.iter())
+  }
+}
+
+impl<T: Eq, S: StackBackend<T>> Eq for List<T, S> {}
+
+impl<T, S: StackBackend<T>> Index<usize> for List<T, S> {
+  type Output = T;
+
+  fn index(&self, index: usize) -> &T {
+    let len = self.len();
+    self.get(index).unwrap_or_else(|| panic!("index {} out of bounds for a list of length {}", index, len))
+  }
+}
+
+impl<T, S: StackBackend<T>> IndexMut<usize> for List<T, S> {
+  fn index_mut(&mut self, index: usize) -> &mut T {
+    let len = self.len();
+    self.get_mut(index).unwrap_or_else(|| panic!("index {} out of bounds for a list of length {}", index, len))
+  }
+}
+
+// prints like the diagrams in the tests, with _ for the finger: [0, 2, 3, _, 4, 1]
+impl<T: fmt::Debug, S: StackBackend<T>> fmt::Debug for List<T, S> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let left: Vec<_> = self.left.top_down().collect();
+    f.debug_list()
+      .entries(left.iter().rev())
+      .entry(&format_args!("_"))
+      .entries(self.right.top_down())
+      .finish()
+  }
+}
+
+// for people rather than debugging: the elements in order with spaces between, "0 2 3 4 1". {:#} marks the
+// finger with a |, "0 2 3 | 4 1"
+impl<T: fmt::Display, S: StackBackend<T>> fmt::Display for List<T, S> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let position = self.position();
+    for (i, elem) in self.iter().enumerate() {
+      if i > 0 {
+        f.write_str(" ")?;
+      }
+      if f.alternate() && i == position {
+        f.write_str("| ")?;
+      }
+      fmt::Display::fmt(elem, f)?;
+    }
+    if f.alternate() && position == self.len() {
+      f.write_str(if position == 0 { "|" } else { " |" })?;
+    }
+    Ok(())
+  }
+}
+
+// Saved as the elements in order plus where the finger was, so a buffer comes back exactly as it was left:
+// {"elems": [0, 2, 3, 4, 1], "cursor": 3}
+#[cfg(feature = "serde")]
+mod serde_impls {
+  use super::{List, StackBackend};
+  use serde::de::{Deserialize, Deserializer, Error};
+  use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+  struct Elems<'a, T, B>(&'a List<T, B>);
+
+  impl<'a, T: Serialize, B: StackBackend<T>> Serialize for Elems<'a, T, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.collect_seq(self.0.iter())
+    }
+  }
+
+  impl<T: Serialize, B: StackBackend<T>> Serialize for List<T, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+      let mut state = serializer.serialize_struct("List", 2)?;
+      state.serialize_field("elems", &Elems(self))?;
+      state.serialize_field("cursor", &self.position())?;
+      state.end()
+    }
+  }
+
+  #[derive(serde::Deserialize)]
+  #[serde(rename = "List")]
+  struct Repr<T> {
+    elems: Vec<T>,
+    cursor: usize,
+  }
+
+  impl<'de, T: Deserialize<'de>, B: StackBackend<T>> Deserialize<'de> for List<T, B> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+      let repr = Repr::deserialize(deserializer)?;
+      if repr.cursor > repr.elems.len() {
+        return Err(D::Error::custom(format_args!(
+          "cursor {} is past the end of a list of length {}", repr.cursor, repr.elems.len()
+        )));
+      }
+      let mut list = List::with_backend();
+      list.extend(repr.elems);
+      list.seek(repr.cursor);
+      Ok(list)
+    }
+  }
+}
+
+// same trick as iter: the left stack gets popped into a Vec first so it comes out in order
+pub struct IntoIter<T, S = Stack<T>> {
+  left: Vec<T>,
+  right: S,
+}
+
+impl<T, S: StackBackend<T>> IntoIterator for List<T, S> {
+  type Item = T;
+  type IntoIter = IntoIter<T, S>;
+
+  fn into_iter(mut self) -> Self::IntoIter {
+    let mut left = Vec::new();
+    while let Some(elem) = self.left.pop() {
+      left.push(elem);
+    }
+    IntoIter { left, right: self.right }
+  }
+}
+
+impl<T, S: StackBackend<T>> Iterator for IntoIter<T, S> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.left.pop().or_else(|| self.right.pop())
+  }
+}
+
+pub struct Drain<'a, T, S = Stack<T>> {
+  stack: &'a mut S,
+  marker: PhantomData<T>,
+}
+
+impl<'a, T, S: StackBackend<T>> Iterator for Drain<'a, T, S> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.stack.pop()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.stack.len(), Some(self.stack.len()))
+  }
+}
+
+impl<'a, T, S: StackBackend<T>> ExactSizeIterator for Drain<'a, T, S> {}
+
+// One side of the finger, seen from the finger. Walking the stack gets us the elements nearest the finger first for
+// free, but going front to back means starting from the far end of the left stack, and going back to front means
+// starting from the far end of the right stack. Whenever someone wants the far end we stash whatever is left of the
+// walk, and keep taking from both ends of that instead.
+//
+// So a plain front to back iter only ever buffers the left stack, and rev() only ever buffers the right stack.
+struct Half<I: Iterator> {
+  near: I,
+  far: VecDeque<I::Item>,
+  len: usize,
+}
+
+impl<I: Iterator> Half<I> {
+  fn new(near: I, len: usize) -> Self {
+    Half { near, far: VecDeque::new(), len }
+  }
+
+  fn take_near(&mut self) -> Option<I::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    self.len -= 1;
+    if self.far.is_empty() {
+      self.near.next()
+    } else {
+      self.far.pop_front()
+    }
+  }
+
+  fn take_far(&mut self) -> Option<I::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    if self.far.is_empty() {
+      self.far.extend(self.near.by_ref().take(self.len));
+    }
+    self.len -= 1;
+    self.far.pop_back()
+  }
+}
+
+pub struct Iter<'a, T: 'a, S: StackBackend<T> + 'a = Stack<T>> {
+  left: Half<S::Iter<'a>>,
+  right: Half<S::Iter<'a>>,
+}
+
+impl<'a, T, S: StackBackend<T>> Iterator for Iter<'a, T, S> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.left.take_far().or_else(|| self.right.take_near())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.left.len + self.right.len;
+    (len, Some(len))
+  }
+}
+
+impl<'a, T, S: StackBackend<T>> DoubleEndedIterator for Iter<'a, T, S> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.right.take_far().or_else(|| self.left.take_near())
+  }
+}
+
+impl<'a, T, S: StackBackend<T>> ExactSizeIterator for Iter<'a, T, S> {}
+
+pub struct IterMut<'a, T: 'a, S: StackBackend<T> + 'a = Stack<T>> {
+  left: Half<S::IterMut<'a>>,
+  right: Half<S::IterMut<'a>>,
+}
+
+impl<'a, T, S: StackBackend<T>> Iterator for IterMut<'a, T, S> {
+  type Item = &'a mut T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.left.take_far().or_else(|| self.right.take_near())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.left.len + self.right.len;
+    (len, Some(len))
+  }
+}
+
+impl<'a, T, S: StackBackend<T>> DoubleEndedIterator for IterMut<'a, T, S> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.right.take_far().or_else(|| self.left.take_near())
+  }
+}
+
+impl<'a, T, S: StackBackend<T>> ExactSizeIterator for IterMut<'a, T, S> {}
+
+// What the finger list needs out of each half. The top of the stack is the end touching the finger, and anything
+// that walks the stack goes top to bottom. The walks aren't called iter so they can't shadow slice::iter on a Vec.
+pub trait StackBackend<T>: Default {
+  type Iter<'a>: Iterator<Item = &'a T> where Self: 'a, T: 'a;
+  type IterMut<'a>: Iterator<Item = &'a mut T> where Self: 'a, T: 'a;
+
+  fn push(&mut self, elem: T);
+  fn pop(&mut self) -> Option<T>;
+  fn peek(&self) -> Option<&T>;
+  fn peek_mut(&mut self) -> Option<&mut T>;
+  fn len(&self) -> usize;
+  fn top_down(&self) -> Self::Iter<'_>;
+  fn top_down_mut(&mut self) -> Self::IterMut<'_>;
+  fn remove_nth(&mut self, n: usize) -> Option<T>;
+  fn retain<F>(&mut self, pred: F) where F: FnMut(&T) -> bool;
+
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  // put all of other underneath us, so our bottom ends up sitting on other's top
+  fn append_below(&mut self, mut other: Self) {
+    let mut flipped = Self::default();
+    while self.shift_to(&mut flipped) {}
+    while flipped.shift_to(&mut other) {}
+    *self = other;
+  }
+
+  // move our top onto other's top, false if we're empty. this is what go_left and go_right are made of
+  fn shift_to(&mut self, other: &mut Self) -> bool {
+    match self.pop() {
+      Some(elem) => {
+        other.push(elem);
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+impl<T> StackBackend<T> for Stack<T> {
+  type Iter<'a> = StackIter<'a, T> where T: 'a;
+  type IterMut<'a> = StackIterMut<'a, T> where T: 'a;
+
+  fn push(&mut self, elem: T) {
+    Stack::push(self, elem)
+  }
+
+  fn pop(&mut self) -> Option<T> {
+    Stack::pop(self)
+  }
+
+  fn peek(&self) -> Option<&T> {
+    Stack::peek(self)
+  }
+
+  fn peek_mut(&mut self) -> Option<&mut T> {
+    Stack::peek_mut(self)
+  }
+
+  fn len(&self) -> usize {
+    Stack::len(self)
+  }
+
+  fn top_down(&self) -> Self::Iter<'_> {
+    Stack::iter(self)
+  }
+
+  fn top_down_mut(&mut self) -> Self::IterMut<'_> {
+    Stack::iter_mut(self)
+  }
+
+  fn remove_nth(&mut self, n: usize) -> Option<T> {
+    Stack::remove_nth(self, n)
+  }
+
+  fn retain<F>(&mut self, pred: F) where F: FnMut(&T) -> bool {
+    Stack::retain(self, pred)
+  }
+
+  fn is_empty(&self) -> bool {
+    Stack::is_empty(self)
+  }
+
+  fn append_below(&mut self, other: Self) {
+    Stack::append_below(self, other)
+  }
+
+  // relink the node instead of unboxing and reboxing the element
+  fn shift_to(&mut self, other: &mut Self) -> bool {
+    self.pop_node().map(|node| {
+      other.push_node(node);
     }).is_some()
   }
 }
 
+// the top is the end of the Vec
+impl<T> StackBackend<T> for Vec<T> {
+  type Iter<'a> = Rev<slice::Iter<'a, T>> where T: 'a;
+  type IterMut<'a> = Rev<slice::IterMut<'a, T>> where T: 'a;
+
+  fn push(&mut self, elem: T) {
+    Vec::push(self, elem)
+  }
+
+  fn pop(&mut self) -> Option<T> {
+    Vec::pop(self)
+  }
+
+  fn peek(&self) -> Option<&T> {
+    self.last()
+  }
+
+  fn peek_mut(&mut self) -> Option<&mut T> {
+    self.last_mut()
+  }
+
+  fn len(&self) -> usize {
+    Vec::len(self)
+  }
+
+  fn top_down(&self) -> Self::Iter<'_> {
+    self.as_slice().iter().rev()
+  }
+
+  fn top_down_mut(&mut self) -> Self::IterMut<'_> {
+    self.as_mut_slice().iter_mut().rev()
+  }
+
+  fn remove_nth(&mut self, n: usize) -> Option<T> {
+    let len = Vec::len(self);
+    if n < len { Some(self.remove(len - 1 - n)) } else { None }
+  }
+
+  // copies all of us onto the end of other, so O(len of self) either way
+  fn append_below(&mut self, mut other: Self) {
+    other.append(self);
+    *self = other;
+  }
+
+  // Vec::retain goes bottom to top, and pred has to see things top to bottom
+  fn retain<F>(&mut self, mut pred: F) where F: FnMut(&T) -> bool {
+    self.reverse();
+    Vec::retain(self, |elem| pred(elem));
+    self.reverse();
+  }
+}
+
 pub struct Stack<T> {
   head: Link<T>,
+  len: usize,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
@@ -84,12 +881,12 @@ struct Node<T> {
 
 impl<T> Stack<T> {
   pub fn new() -> Self {
-    Stack { head: None }
+    Stack { head: None, len: 0 }
   }
 
  pub fn push(&mut self, elem: T) {
    let new_node = Box::new(Node {
-     elem: elem,
+     elem,
      next: None,
    });
 
@@ -99,6 +896,7 @@ impl<T> Stack<T> {
  fn push_node(&mut self, mut node: Box<Node<T>>) {
    node.next = self.head.take();
    self.head = Some(node);
+   self.len += 1;
  }
 
  pub fn pop(&mut self) -> Option<T> {
@@ -110,10 +908,55 @@ impl<T> Stack<T> {
  fn pop_node(&mut self) -> Option<Box<Node<T>>> {
   self.head.take().map(|mut node| {
     self.head = node.next.take();
+    self.len -= 1;
     node
   })
  }
 
+ // n = 0 is the top
+ pub fn remove_nth(&mut self, n: usize) -> Option<T> {
+   let mut link = &mut self.head;
+   for _ in 0..n {
+     link = &mut link.as_mut()?.next;
+   }
+   let mut node = link.take()?;
+   *link = node.next.take();
+   self.len -= 1;
+   Some(node.elem)
+ }
+
+ // top to bottom, unlinking whatever pred says no to
+ pub fn retain<F>(&mut self, mut pred: F) where F: FnMut(&T) -> bool {
+   let mut link = &mut self.head;
+   while link.is_some() {
+     if pred(&link.as_ref().unwrap().elem) {
+       link = &mut link.as_mut().unwrap().next;
+     } else {
+       let mut node = link.take().unwrap();
+       *link = node.next.take();
+       self.len -= 1;
+     }
+   }
+ }
+
+ // walk to the bottom and hang other's nodes off it. O(len of self) for the walk, other's nodes just get relinked
+ pub fn append_below(&mut self, mut other: Stack<T>) {
+   let mut link = &mut self.head;
+   while link.is_some() {
+     link = &mut link.as_mut().unwrap().next;
+   }
+   *link = other.head.take();
+   self.len += mem::take(&mut other.len);
+ }
+
+ pub fn len(&self) -> usize {
+   self.len
+ }
+
+ pub fn is_empty(&self) -> bool {
+   self.head.is_none()
+ }
+
  pub fn peek(&self) -> Option<&T> {
   self.head.as_ref().map(|node| {
     &node.elem
@@ -125,6 +968,63 @@ impl<T> Stack<T> {
      &mut node.elem
    })
  }
+
+ // top to bottom
+ pub fn iter(&self) -> StackIter<'_, T> {
+   StackIter { next: self.head.as_deref() }
+ }
+
+ pub fn iter_mut(&mut self) -> StackIterMut<'_, T> {
+   StackIterMut { next: self.head.as_deref_mut() }
+ }
+}
+
+pub struct StackIter<'a, T> {
+  next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for StackIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next.map(|node| {
+      self.next = node.next.as_deref();
+      &node.elem
+    })
+  }
+}
+
+pub struct StackIterMut<'a, T> {
+  next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for StackIterMut<'a, T> {
+  type Item = &'a mut T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next.take().map(|node| {
+      self.next = node.next.as_deref_mut();
+      &mut node.elem
+    })
+  }
+}
+
+// pushing flips the order, so clone bottom-up
+impl<T: Clone> Clone for Stack<T> {
+  fn clone(&self) -> Self {
+    let elems: Vec<_> = self.iter().collect();
+    let mut stack = Stack::new();
+    for elem in elems.into_iter().rev() {
+      stack.push(elem.clone());
+    }
+    stack
+  }
+}
+
+impl<T> Default for Stack<T> {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl<T> Drop for Stack<T> {
@@ -169,4 +1069,637 @@ mod test {
     assert_eq!(list.pop_right(), None);
     assert_eq!(list.pop_left(), None);
   }
+
+  #[test]
+  fn len() {
+    let mut list = List::new();
+    assert_eq!((list.len(), list.left_len(), list.right_len()), (0, 0, 0));
+
+    list.push_left(0);
+    list.push_left(2);
+    list.push_right(1);                       // [0, 2, _, 1]
+    assert_eq!((list.len(), list.left_len(), list.right_len()), (3, 2, 1));
+    assert!(!list.is_empty());
+
+    while list.go_left() {}                   // [_, 0, 2, 1]
+    assert_eq!((list.len(), list.left_len(), list.right_len()), (3, 0, 3));
+
+    list.go_right();
+    list.pop_right();                         // [0, _, 1]
+    assert_eq!((list.len(), list.left_len(), list.right_len()), (2, 1, 1));
+
+    list.pop_left();
+    list.pop_right();
+    list.pop_right();
+    assert_eq!((list.len(), list.left_len(), list.right_len()), (0, 0, 0));
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn seek() {
+    let mut list = List::new();
+    for i in 0..5 {
+      list.push_left(i);
+    }                                         // [0, 1, 2, 3, 4, _]
+
+    assert_eq!(list.position(), 5);
+    assert!(list.seek(2));                    // [0, 1, _, 2, 3, 4]
+    assert_eq!(list.position(), 2);
+    assert_eq!(list.peek_left(), Some(&1));
+    assert_eq!(list.peek_right(), Some(&2));
+
+    assert!(list.seek(4));                    // [0, 1, 2, 3, _, 4]
+    assert_eq!(list.peek_right(), Some(&4));
+
+    assert!(list.seek(0));                    // [_, 0, 1, 2, 3, 4]
+    assert_eq!(list.peek_left(), None);
+
+    assert!(!list.seek(6));
+    assert_eq!(list.position(), 0);
+    assert_eq!(list.peek_right(), Some(&0));
+
+    assert!(list.seek(5));                    // [0, 1, 2, 3, 4, _]
+    assert_eq!(list.peek_right(), None);
+  }
+
+  #[test]
+  fn remove_nth() {
+    let mut list = List::new();
+    for i in 0..3 {
+      list.push_left(i);
+    }
+    for i in (3..6).rev() {
+      list.push_right(i);
+    }                                         // [0, 1, 2, _, 3, 4, 5]
+
+    assert_eq!(list.remove_left_nth(1), Some(1));   // [0, 2, _, 3, 4, 5]
+    assert_eq!(list.remove_right_nth(2), Some(5));  // [0, 2, _, 3, 4]
+    assert_eq!(list.remove_right_nth(0), Some(3));  // [0, 2, _, 4]
+    assert_eq!(list.remove_left_nth(2), None);
+    assert_eq!(list.remove_right_nth(1), None);
+
+    assert_eq!(list.position(), 2);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4]);
+  }
+
+  #[test]
+  fn splice() {
+    let mut list = List::new();
+    list.push_left(0);
+    list.push_right(4);                       // [0, _, 4]
+
+    let mut other = List::new();
+    other.push_left(1);
+    other.push_left(2);
+    other.push_right(3);                      // [1, 2, _, 3]
+
+    list.splice(other);                       // [0, 1, 2, 3, _, 4]
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(list.position(), 4);
+    assert_eq!(list.peek_left(), Some(&3));
+    assert_eq!(list.peek_right(), Some(&4));
+
+    list.splice(List::new());
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.position(), 4);
+  }
+
+  #[test]
+  fn split_off() {
+    let mut list = List::new();
+    list.push_left(0);
+    list.push_left(1);
+    list.push_right(3);
+    list.push_right(2);                       // [0, 1, _, 2, 3]
+
+    let mut cut = list.split_off();           // [0, 1, _] and [_, 2, 3]
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(list.peek_right(), None);
+    assert_eq!(cut.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!(cut.position(), 0);
+
+    // cut and paste back in somewhere else
+    list.seek(0);
+    list.splice(cut.split_off());             // [2, 3, _, 0, 1]
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 0, 1]);
+    assert!(cut.is_empty());
+  }
+
+  #[test]
+  fn debug() {
+    let mut list = List::new();
+    assert_eq!(format!("{:?}", list), "[_]");
+
+    list.push_left(0);
+    list.push_left(2);
+    list.push_left(3);
+    list.push_right(1);
+    list.push_right(4);
+    assert_eq!(format!("{:?}", list), "[0, 2, 3, _, 4, 1]");
+
+    while list.go_left() {}
+    assert_eq!(format!("{:?}", list), "[_, 0, 2, 3, 4, 1]");
+  }
+
+  #[test]
+  fn vec_conversions() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(format!("{:?}", list), "[_, 1, 2, 3]");
+    assert_eq!(list.into_vec(), vec![1, 2, 3]);
+
+    let list = List::from_vec(vec![1, 2, 3], Some(2));
+    assert_eq!(format!("{:?}", list), "[1, 2, _, 3]");
+    assert_eq!(Vec::from(list), vec![1, 2, 3]);
+
+    let list = List::from_vec(vec![1, 2, 3], Some(10));
+    assert_eq!(list.position(), 3);
+
+    let list = List::<i32>::from(Vec::new());
+    assert!(list.is_empty());
+    assert!(list.into_vec().is_empty());
+  }
+
+  #[test]
+  fn bulk_moves() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);   // [_, 0, 1, 2, 3, 4]
+
+    assert_eq!(list.go_right_n(2), 2);                // [0, 1, _, 2, 3, 4]
+    assert_eq!(list.peek_right(), Some(&2));
+    assert_eq!(list.go_right_n(10), 3);               // [0, 1, 2, 3, 4, _]
+    assert_eq!(list.go_left_n(1), 1);                 // [0, 1, 2, 3, _, 4]
+    assert_eq!(list.peek_right(), Some(&4));
+
+    assert_eq!(list.seek_front(), 4);                 // [_, 0, 1, 2, 3, 4]
+    assert_eq!(list.seek_front(), 0);
+    assert_eq!(list.go_left_n(3), 0);
+    assert_eq!(list.seek_back(), 5);                  // [0, 1, 2, 3, 4, _]
+    assert_eq!(list.position(), 5);
+  }
+
+  #[test]
+  fn retain() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4, 5, 6], Some(4));   // [0, 1, 2, 3, _, 4, 5, 6]
+
+    let mut seen = Vec::new();
+    list.retain(|&x| {
+      seen.push(x);
+      x % 2 == 0
+    });
+    assert_eq!(seen, vec![0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(format!("{:?}", list), "[0, 2, _, 4, 6]");
+
+    list.retain(|&x| x > 2);
+    assert_eq!(format!("{:?}", list), "[_, 4, 6]");
+
+    list.retain(|_| false);
+    assert!(list.is_empty());
+    assert_eq!(list.position(), 0);
+  }
+
+  #[test]
+  fn swap_across() {
+    let mut list = List::from_vec(vec!['a', 'b', 'c'], Some(1));   // [a, _, b, c]
+    assert!(list.swap_across());                                   // [b, _, a, c]
+    assert_eq!(format!("{:?}", list), "['b', _, 'a', 'c']");
+
+    list.go_right();                                               // [b, a, _, c]
+    assert!(list.swap_across());                                   // [b, c, _, a]
+    assert_eq!(format!("{:?}", list), "['b', 'c', _, 'a']");
+
+    list.seek_back();
+    assert!(!list.swap_across());
+    list.seek_front();
+    assert!(!list.swap_across());
+  }
+
+  #[test]
+  fn clone() {
+    let mut list = List::from_vec(vec![1, 2, 3, 4], Some(2));
+    let snapshot = list.clone();
+    assert_eq!(format!("{:?}", snapshot), "[1, 2, _, 3, 4]");
+
+    list.pop_left();
+    list.push_right(5);
+    *list.peek_left_mut().unwrap() = 10;
+    assert_eq!(format!("{:?}", list), "[10, _, 5, 3, 4]");
+    assert_eq!(format!("{:?}", snapshot), "[1, 2, _, 3, 4]");
+    assert_eq!(snapshot.len(), 4);
+  }
+
+  #[test]
+  fn eq() {
+    let a = List::from_vec(vec![1, 2, 3], Some(1));
+    let b = List::from_vec(vec![1, 2, 3], Some(3));
+    assert_eq!(a, b);
+    assert!(!a.eq_with_cursor(&b));
+    assert!(a.eq_with_cursor(&a.clone()));
+
+    assert_ne!(a, List::from(vec![1, 2]));
+    assert_ne!(a, List::from(vec![1, 2, 4]));
+    assert_eq!(List::<i32>::new(), List::new());
+  }
+
+  #[test]
+  fn peek_nth() {
+    let list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));     // [0, 1, _, 2, 3, 4]
+    assert_eq!(list.peek_nth_left(0), Some(&1));
+    assert_eq!(list.peek_nth_left(1), Some(&0));
+    assert_eq!(list.peek_nth_left(2), None);
+    assert_eq!(list.peek_nth_right(0), Some(&2));
+    assert_eq!(list.peek_nth_right(2), Some(&4));
+    assert_eq!(list.peek_nth_right(3), None);
+    assert_eq!(list.position(), 2);
+  }
+
+  #[test]
+  fn context() {
+    let list = List::from_vec(vec![0, 1, 2, 3, 4, 5], Some(3));  // [0, 1, 2, _, 3, 4, 5]
+    assert_eq!(list.context(2), (vec![&1, &2], vec![&3, &4]));
+    assert_eq!(list.context(10), (vec![&0, &1, &2], vec![&3, &4, &5]));
+    assert_eq!(list.context(0), (vec![], vec![]));
+
+    let list = List::from(vec![0, 1]);
+    assert_eq!(list.context(1), (vec![], vec![&0]));
+  }
+
+  #[test]
+  fn extend() {
+    let mut list = List::from_vec(vec![0, 4], Some(1));          // [0, _, 4]
+    list.extend(vec![1, 2, 3]);                                  // [0, _, 1, 2, 3, 4]
+    assert_eq!(format!("{:?}", list), "[0, _, 1, 2, 3, 4]");
+
+    list.seek_back();
+    list.extend(5..7);
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, _, 5, 6]");
+  }
+
+  #[test]
+  fn reverse() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));   // [0, 1, _, 2, 3, 4]
+    list.reverse();                                                // [4, 3, 2, _, 1, 0]
+    assert_eq!(format!("{:?}", list), "[4, 3, 2, _, 1, 0]");
+    assert_eq!(list.position(), 3);
+    assert_eq!(list.peek_left(), Some(&2));
+    assert_eq!(list.peek_right(), Some(&1));
+
+    // finger at an end ends up at the other end
+    list.seek_front();
+    list.reverse();
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, _]");
+
+    list.reverse();
+    list.reverse();
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, _]");
+  }
+
+  #[test]
+  fn rotate() {
+    let mut list = List::from(vec![0, 1, 2]);                    // [_, 0, 1, 2]
+
+    // round robin: step and look at what we stepped over
+    let mut seen = Vec::new();
+    for _ in 0..7 {
+      assert!(list.rotate_right());
+      seen.push(*list.peek_left().unwrap());
+    }
+    assert_eq!(seen, vec![0, 1, 2, 0, 1, 2, 0]);
+    assert_eq!(format!("{:?}", list), "[0, _, 1, 2]");
+
+    assert!(list.rotate_left());                                 // [_, 0, 1, 2]
+    assert!(list.rotate_left());                                 // [0, 1, _, 2]
+    assert_eq!(format!("{:?}", list), "[0, 1, _, 2]");
+
+    let mut empty = List::<i32>::new();
+    assert!(!empty.rotate_left());
+    assert!(!empty.rotate_right());
+  }
+
+  #[test]
+  fn drain() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4, 5], Some(3));  // [0, 1, 2, _, 3, 4, 5]
+
+    assert_eq!(list.drain_left().collect::<Vec<_>>(), vec![2, 1, 0]);
+    assert_eq!(format!("{:?}", list), "[_, 3, 4, 5]");
+
+    assert_eq!(list.drain_right().take(2).collect::<Vec<_>>(), vec![3, 4]);
+    assert_eq!(format!("{:?}", list), "[_, 5]");
+    assert_eq!(list.drain_right().len(), 1);
+    assert_eq!(list.drain_left().next(), None);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde() {
+    let list = List::from_vec(vec![0, 2, 3, 4, 1], Some(3));
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, r#"{"elems":[0,2,3,4,1],"cursor":3}"#);
+
+    let back: List<i32> = serde_json::from_str(&json).unwrap();
+    assert!(back.eq_with_cursor(&list));
+
+    assert!(serde_json::from_str::<List<i32>>(r#"{"elems":[1],"cursor":2}"#).is_err());
+  }
+
+  #[test]
+  fn vec_backend() {
+    let mut list = List::<i32, Vec<i32>>::with_backend();   // [_]
+
+    list.push_left(0);                                      // [0,_]
+    list.push_right(1);                                     // [0,_1]
+    list.push_left(2);                                      // [0, 2, _, 1]
+    list.push_left(3);                                      // [0, 2, 3, _, 1]
+    list.push_right(4);                                     // [0, 2, 3, _, 4, 1]
+    assert_eq!(format!("{:?}", list), "[0, 2, 3, _, 4, 1]");
+    assert_eq!(list.peek_nth_left(1), Some(&2));
+    assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![1, 4, 3, 2, 0]);
+
+    while list.go_left() {}                                 // [_, 0, 2, 3, 4, 1]
+    assert_eq!(list.pop_right(), Some(0));                  // [_, 2, 3, 4, 1]
+    list.go_right_n(2);                                     // [2, 3, _, 4, 1]
+    assert_eq!(list.remove_left_nth(1), Some(2));           // [3, _, 4, 1]
+
+    let mut seen = Vec::new();
+    list.retain(|&x| {
+      seen.push(x);
+      x != 4
+    });
+    assert_eq!(seen, vec![3, 4, 1]);
+    assert_eq!(format!("{:?}", list), "[3, _, 1]");
+
+    let mut other = List::<i32, Vec<i32>>::with_backend();
+    other.extend(vec![5, 6]);
+    list.splice(other);                                     // [3, 5, 6, _, 1]
+    let tail = list.split_off();
+    assert_eq!(list.into_vec(), vec![3, 5, 6]);
+    assert_eq!(tail.into_vec(), vec![1]);
+  }
+
+  #[test]
+  fn replace() {
+    let mut list = List::from_vec(vec![1, 2, 3], Some(1));       // [1, _, 2, 3]
+    assert_eq!(list.replace_left(10), Some(1));
+    assert_eq!(list.replace_right(20), Some(2));
+    assert_eq!(format!("{:?}", list), "[10, _, 20, 3]");
+
+    list.seek_back();
+    assert_eq!(list.replace_right(4), None);
+    assert_eq!(format!("{:?}", list), "[10, 20, 3, _, 4]");
+    assert_eq!(list.len(), 4);
+  }
+
+  #[test]
+  fn index() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));   // [0, 1, _, 2, 3, 4]
+    for i in 0..5 {
+      assert_eq!(list[i], i);
+    }
+    assert_eq!(list.get(5), None);
+
+    list[0] = 10;
+    list[4] = 40;
+    *list.get_mut(2).unwrap() = 20;
+    assert_eq!(format!("{:?}", list), "[10, 1, _, 20, 3, 40]");
+    assert_eq!(list.get_mut(7), None);
+  }
+
+  #[test]
+  #[should_panic]
+  fn index_out_of_bounds() {
+    let list = List::from(vec![0, 1]);
+    let _ = list[2];
+  }
+
+  #[test]
+  fn from_iter() {
+    let list: List<_> = (0..4).collect();
+    assert_eq!(format!("{:?}", list), "[_, 0, 1, 2, 3]");
+
+    let list: List<_, Vec<_>> = "abc".chars().collect();
+    assert_eq!(format!("{:?}", list), "[_, 'a', 'b', 'c']");
+  }
+
+  #[test]
+  fn clear() {
+    let mut list = List::from_vec(vec![1, 2, 3], Some(2));
+    assert!(!list.is_empty());
+    list.clear();
+    assert!(list.is_empty());
+    assert_eq!(list.position(), 0);
+    assert_eq!(list, List::default());
+
+    // long enough that recursive drops would blow the stack
+    let mut list: List<_> = (0..200_000).collect();
+    list.seek(100_000);
+    list.clear();
+    assert_eq!(list.len(), 0);
+
+    let list: List<i32, Vec<i32>> = Default::default();
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn display() {
+    let mut list = List::from_vec(vec![0, 2, 3, 4, 1], Some(3));
+    assert_eq!(list.to_string(), "0 2 3 4 1");
+    assert_eq!(format!("{:#}", list), "0 2 3 | 4 1");
+
+    list.seek_front();
+    assert_eq!(format!("{:#}", list), "| 0 2 3 4 1");
+    list.seek_back();
+    assert_eq!(format!("{:#}", list), "0 2 3 4 1 |");
+
+    let empty = List::<i32>::new();
+    assert_eq!(empty.to_string(), "");
+    assert_eq!(format!("{:#}", empty), "|");
+  }
+
+  #[test]
+  fn iter_sides() {
+    let list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));     // [0, 1, _, 2, 3, 4]
+    assert_eq!(list.iter_left().collect::<Vec<_>>(), vec![&1, &0]);
+    assert_eq!(list.iter_right().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+    // e.g. how far back is the nearest even number
+    assert_eq!(list.iter_left().position(|x| x % 2 == 0), Some(1));
+  }
+
+  #[test]
+  fn map_around() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4, 5, 6], Some(3));   // [0, 1, 2, _, 3, 4, 5, 6]
+
+    let mut seen = Vec::new();
+    list.map_around(2, |x| {
+      seen.push(*x);
+      *x *= 10;
+    });
+    assert_eq!(seen, vec![1, 2, 3, 4]);
+    assert_eq!(format!("{:?}", list), "[0, 10, 20, _, 30, 40, 5, 6]");
+
+    list.map_around(100, |x| *x += 1);
+    assert_eq!(format!("{:?}", list), "[1, 11, 21, _, 31, 41, 6, 7]");
+  }
+
+  #[test]
+  fn cursor_tokens() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));
+    let token = list.save_cursor();
+    assert_eq!(token.position(), 2);
+
+    list.seek_back();
+    list.push_left(5);
+    assert!(list.restore_cursor(token));
+    assert_eq!(format!("{:?}", list), "[0, 1, _, 2, 3, 4, 5]");
+
+    list.seek_back();
+    let end = list.save_cursor();
+    list.clear();
+    assert!(!list.restore_cursor(end));
+    assert_eq!(list.position(), 0);
+  }
+
+  #[test]
+  fn append_prepend() {
+    let mut list = List::from_vec(vec![2, 3, 4], Some(1));       // [2, _, 3, 4]
+    list.append(List::from_vec(vec![5, 6], Some(1)));
+    assert_eq!(format!("{:?}", list), "[2, _, 3, 4, 5, 6]");
+
+    list.prepend(List::from_vec(vec![0, 1], Some(1)));
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, _, 3, 4, 5, 6]");
+    assert_eq!(list.len(), 7);
+    assert_eq!(list.position(), 3);
+
+    list.append(List::new());
+    list.prepend(List::new());
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, _, 3, 4, 5, 6]");
+
+    let mut list = List::<i32, Vec<i32>>::with_backend();
+    list.append((3..5).collect());
+    list.prepend((0..3).collect());
+    list.seek(2);
+    assert_eq!(format!("{:?}", list), "[0, 1, _, 2, 3, 4]");
+  }
+
+  #[test]
+  fn move_current() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));   // [0, 1, _, 2, 3, 4]
+    assert!(list.move_current_to_front());
+    assert_eq!(format!("{:?}", list), "[2, 0, 1, _, 3, 4]");
+
+    assert!(list.move_current_to_back());
+    assert_eq!(format!("{:?}", list), "[2, 0, 1, _, 4, 3]");
+
+    list.seek_back();
+    assert!(!list.move_current_to_front());
+    assert!(!list.move_current_to_back());
+
+    // move-to-front: look something up, then bump it
+    let mut list: List<_> = "abcde".chars().collect();
+    list.go_right_n(3);
+    list.move_current_to_front();
+    list.seek_front();
+    assert_eq!(list.iter().collect::<String>(), "dabce");
+  }
+
+  #[test]
+  fn find() {
+    let mut list = List::from_vec(vec![0, 1, 2, 3, 4, 5], Some(2));   // [0, 1, _, 2, 3, 4, 5]
+
+    assert!(list.find_forward(|&x| x == 2));
+    assert_eq!(list.position(), 2);
+    assert!(list.find_forward(|&x| x > 3));
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, _, 4, 5]");
+
+    assert!(list.find_backward(|&x| x % 2 == 1));
+    assert_eq!(format!("{:?}", list), "[0, 1, 2, _, 3, 4, 5]");
+    assert!(list.find_backward(|&x| x == 0));
+    assert_eq!(format!("{:?}", list), "[_, 0, 1, 2, 3, 4, 5]");
+
+    assert!(!list.find_backward(|_| true));
+    assert!(!list.find_forward(|&x| x > 10));
+    assert_eq!(list.position(), 6);
+  }
+
+  #[test]
+  fn iter() {
+    let mut list = List::new();
+    assert_eq!(list.iter().next(), None);
+
+    list.push_left(0);
+    list.push_left(2);
+    list.push_left(3);
+    list.push_right(1);
+    list.push_right(4);                       // [0, 2, 3, _, 4, 1]
+
+    let elems: Vec<_> = list.iter().copied().collect();
+    assert_eq!(elems, vec![0, 2, 3, 4, 1]);
+
+    // moving the finger doesn't change what we see
+    list.go_left();                           // [0, 2, _, 3, 4, 1]
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), elems);
+    while list.go_right() {}                  // [0, 2, 3, 4, 1, _]
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), elems);
+  }
+
+  #[test]
+  fn into_iter() {
+    let mut list = List::new();
+    list.push_left(1);
+    list.push_left(2);
+    list.push_right(4);
+    list.push_right(3);                       // [1, 2, _, 3, 4]
+
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(List::<i32>::new().into_iter().next(), None);
+  }
+
+  #[test]
+  fn iter_rev() {
+    let list = List::from_vec(vec![0, 1, 2, 3, 4], Some(2));     // [0, 1, _, 2, 3, 4]
+    assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    assert_eq!(list.iter().len(), 5);
+
+    // meet in the middle from both ends, on either side of the finger
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next_back(), Some(&2));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let mut iter = list.iter();
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next(), None);
+
+    let mut list = list;
+    for (i, elem) in list.iter_mut().rev().enumerate() {
+      *elem += i * 10;
+    }
+    assert_eq!(list.into_vec(), vec![40, 31, 22, 13, 4]);
+  }
+
+  #[test]
+  fn iter_mut() {
+    let mut list = List::new();
+    list.push_left(1);
+    list.push_left(2);
+    list.push_right(4);
+    list.push_right(3);                       // [1, 2, _, 3, 4]
+
+    for (i, elem) in list.iter_mut().enumerate() {
+      *elem *= 10;
+      *elem += i;
+    }
+
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 21, 32, 43]);
+    assert_eq!(list.peek_left(), Some(&21));
+    assert_eq!(list.peek_right(), Some(&32));
+  }
 }
@@ -0,0 +1,175 @@
+// A text buffer is just the finger list with chars in it. The finger is the text cursor, and since the two stacks
+// meet there, typing and deleting at the cursor are pushes and pops. That's the same idea as a gap buffer: all the
+// free space sits at the cursor, and moving the cursor means shuffling characters from one side to the other.
+//
+// Positions are in chars, not bytes, and lines/columns count from 0.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::silly1::List;
+
+pub struct TextBuffer {
+  chars: List<char>,
+}
+
+impl TextBuffer {
+  pub fn new() -> Self {
+    TextBuffer { chars: List::new() }
+  }
+
+  pub fn len(&self) -> usize {
+    self.chars.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.chars.is_empty()
+  }
+
+  pub fn cursor(&self) -> usize {
+    self.chars.position()
+  }
+
+  pub fn set_cursor(&mut self, index: usize) -> bool {
+    self.chars.seek(index)
+  }
+
+  // like typing it: the cursor ends up after the inserted text
+  pub fn insert_str(&mut self, s: &str) {
+    for c in s.chars() {
+      self.chars.push_left(c);
+    }
+  }
+
+  // leaves the cursor where the range started. panics if the range is backwards or runs past the end, same as String
+  pub fn delete_range(&mut self, range: Range<usize>) {
+    assert!(range.start <= range.end, "range start is after its end");
+    assert!(range.end <= self.len(), "range end is past the end of the buffer");
+
+    self.chars.seek(range.start);
+    for _ in range {
+      self.chars.pop_right();
+    }
+  }
+
+  // false if there's no such line. a column past the end of the line lands on the end of the line
+  pub fn move_to_line_col(&mut self, line: usize, col: usize) -> bool {
+    let mut line_start = 0;
+    let mut current = 0;
+    let mut index = 0;
+    for c in self.chars.iter() {
+      if current == line {
+        break;
+      }
+      index += 1;
+      if *c == '\n' {
+        current += 1;
+        line_start = index;
+      }
+    }
+    if current != line {
+      return false;
+    }
+
+    let line_len = self.chars.iter().skip(line_start).take_while(|&&c| c != '\n').count();
+    self.chars.seek(line_start + col.min(line_len));
+    true
+  }
+
+  pub fn line_col(&self) -> (usize, usize) {
+    let mut line = 0;
+    let mut col = 0;
+    for &c in self.chars.iter().take(self.cursor()) {
+      if c == '\n' {
+        line += 1;
+        col = 0;
+      } else {
+        col += 1;
+      }
+    }
+    (line, col)
+  }
+}
+
+impl Default for TextBuffer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// the cursor starts at the end, as if the text had just been typed
+impl From<&str> for TextBuffer {
+  fn from(s: &str) -> Self {
+    let mut buffer = TextBuffer::new();
+    buffer.insert_str(s);
+    buffer
+  }
+}
+
+impl fmt::Display for TextBuffer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.chars.iter().try_for_each(|c| write!(f, "{}", c))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::TextBuffer;
+
+  #[test]
+  fn editing() {
+    let mut buffer = TextBuffer::new();
+    assert!(buffer.is_empty());
+
+    buffer.insert_str("hello world");
+    assert_eq!(buffer.cursor(), 11);
+
+    buffer.set_cursor(5);
+    buffer.insert_str(",");
+    assert_eq!(buffer.to_string(), "hello, world");
+    assert_eq!(buffer.cursor(), 6);
+
+    buffer.delete_range(0..7);
+    assert_eq!(buffer.to_string(), "world");
+    assert_eq!(buffer.cursor(), 0);
+
+    buffer.insert_str("hi ");
+    assert_eq!(buffer.to_string(), "hi world");
+    assert_eq!(buffer.len(), 8);
+  }
+
+  #[test]
+  #[should_panic]
+  fn delete_past_end() {
+    let mut buffer = TextBuffer::from("abc");
+    buffer.delete_range(1..4);
+  }
+
+  #[test]
+  fn lines() {
+    let mut buffer = TextBuffer::from("first\nsecond line\n\nlast");
+    assert_eq!(buffer.line_col(), (3, 4));
+
+    assert!(buffer.move_to_line_col(1, 3));
+    assert_eq!(buffer.line_col(), (1, 3));
+    buffer.insert_str("o");
+    assert_eq!(buffer.to_string(), "first\nsecoond line\n\nlast");
+
+    // past the end of the line lands on the newline
+    assert!(buffer.move_to_line_col(0, 100));
+    assert_eq!(buffer.cursor(), 5);
+    assert!(buffer.move_to_line_col(2, 3));
+    assert_eq!(buffer.line_col(), (2, 0));
+
+    assert!(!buffer.move_to_line_col(4, 0));
+    assert_eq!(buffer.line_col(), (2, 0));
+  }
+
+  #[test]
+  fn unicode() {
+    let mut buffer = TextBuffer::from("héllo");
+    assert_eq!(buffer.len(), 5);
+    buffer.delete_range(1..2);
+    assert_eq!(buffer.to_string(), "hllo");
+  }
+}
@@ -18,7 +18,7 @@ impl<T> List<T> {
 
   pub fn append(&self, elem: T) -> List<T> {
     List { head: Some(Rc::new(Node {
-      elem: elem,
+      elem,
       next: self.head.clone()
     }))}
   }
@@ -36,6 +36,12 @@ impl<T> List<T> {
   }
 }
 
+impl<T> Default for List<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl<T> Drop for List<T> {
   fn drop(&mut self) {
     let mut head = self.head.take();
@@ -1,3 +1,5 @@
+use std::iter::FromIterator;
+use std::ptr;
 use std::sync::Arc; // much thread safe
 
 pub struct List<T> {
@@ -18,11 +20,18 @@ impl<T> List<T> {
 
   pub fn append(&self, elem: T) -> List<T> {
     List { head: Some(Arc::new(Node {
-      elem: elem,
+      elem,
       next: self.head.clone()
     }))}
   }
 
+  // put elem at the very end. every node's next pointer changes on the way there, so unlike append
+  // nothing can be shared: the whole spine gets copied and self is left alone
+  pub fn snoc(&self, elem: T) -> List<T> where T: Clone {
+    let elems: Vec<&T> = self.iter().collect();
+    elems.into_iter().rev().fold(List::new().append(elem), |list, x| list.append(x.clone()))
+  }
+
   pub fn tail(&self) -> List<T> {
     List { head: self.head.as_ref().and_then(|node| node.next.clone()) }
   }
@@ -34,6 +43,202 @@ impl<T> List<T> {
   pub fn iter(&self) -> Iter<'_, T> {
     Iter { next: self.head.as_deref() }
   }
+
+  pub fn get(&self, index: usize) -> Option<&T> {
+    self.iter().nth(index)
+  }
+
+  // same thing as get, for people coming from Iterator::nth
+  pub fn nth(&self, n: usize) -> Option<&T> {
+    self.get(n)
+  }
+
+  pub fn last(&self) -> Option<&T> {
+    self.iter().last()
+  }
+
+  // the elements of self up to (not including) the first node that also sits in other's spine. once two
+  // persistent lists share a node they share everything after it, so we line the two spines up by length
+  // and walk them together to find that node without hashing anything
+  pub fn iter_until_shared(&self, other: &List<T>) -> IterUntilShared<'_, T> {
+    let mut a = self.head.as_ref();
+    let mut b = other.head.as_ref();
+    let (len_a, len_b) = (self.iter().count(), other.iter().count());
+    for _ in len_b..len_a {
+      a = a.and_then(|node| node.next.as_ref());
+    }
+    for _ in len_a..len_b {
+      b = b.and_then(|node| node.next.as_ref());
+    }
+
+    let stop = loop {
+      match (a, b) {
+        (Some(x), Some(y)) if Arc::ptr_eq(x, y) => break Some(&**x),
+        (Some(x), Some(y)) => {
+          a = x.next.as_ref();
+          b = y.next.as_ref();
+        }
+        _ => break None,
+      }
+    };
+
+    IterUntilShared { next: self.head.as_deref(), stop }
+  }
+
+  // each element along with how many handles (lists or other nodes) point at its node. a count above 1 means
+  // something else points into the list at that spot, and everything from there on is shared with it
+  pub fn iter_with_counts(&self) -> IterWithCounts<'_, T> {
+    IterWithCounts { next: self.head.as_ref() }
+  }
+
+  pub fn sharing_stats(&self) -> SharingStats {
+    let mut stats = SharingStats { nodes: 0, shared_nodes: 0, max_strong_count: 0 };
+    for (_, count) in self.iter_with_counts() {
+      stats.nodes += 1;
+      if count > 1 {
+        stats.shared_nodes += 1;
+      }
+      stats.max_strong_count = stats.max_strong_count.max(count);
+    }
+    stats
+  }
+
+  pub fn contains(&self, elem: &T) -> bool where T: PartialEq {
+    self.iter().any(|x| x == elem)
+  }
+
+  pub fn find<P>(&self, mut pred: P) -> Option<&T> where P: FnMut(&T) -> bool {
+    self.iter().find(|x| pred(x))
+  }
+
+  pub fn position<P>(&self, pred: P) -> Option<usize> where P: FnMut(&T) -> bool {
+    self.iter().position(pred)
+  }
+
+  pub fn starts_with(&self, prefix: &List<T>) -> bool where T: PartialEq {
+    spine_starts_with(self.head.as_ref(), prefix.head.as_ref())
+  }
+
+  // versions of the same list usually share their tails, so after lining up the lengths this tends to
+  // hit the same node right away and stop without comparing any elements
+  pub fn ends_with(&self, suffix: &List<T>) -> bool where T: PartialEq {
+    let (len, suffix_len) = (self.iter().count(), suffix.iter().count());
+    if suffix_len > len {
+      return false;
+    }
+    let mut link = self.head.as_ref();
+    for _ in suffix_len..len {
+      link = link.and_then(|node| node.next.as_ref());
+    }
+    spine_starts_with(link, suffix.head.as_ref())
+  }
+
+  // every suffix of the list, starting with the whole thing and ending with the empty list.
+  // each one is just another handle on the same nodes, so this never copies an element
+  pub fn tails(&self) -> Tails<T> {
+    Tails { next: Some(List { head: self.head.clone() }) }
+  }
+
+  pub fn dedup(&self) -> List<T> where T: PartialEq + Clone {
+    self.dedup_by(|a, b| a == b)
+  }
+
+  // same_bucket gets (current, last kept) like Vec::dedup_by. everything after the last removed
+  // element survives untouched, so the result reuses that suffix and only copies what's in front of it
+  pub fn dedup_by<F>(&self, mut same_bucket: F) -> List<T> where T: Clone, F: FnMut(&T, &T) -> bool {
+    let mut kept = Vec::new();
+    let mut last_removed = None;
+    let mut prev: Option<&T> = None;
+
+    for (i, elem) in self.iter().enumerate() {
+      match prev {
+        Some(prev) if same_bucket(elem, prev) => last_removed = Some(i),
+        _ => {
+          kept.push((i, elem));
+          prev = Some(elem);
+        }
+      }
+    }
+
+    let last_removed = match last_removed {
+      Some(i) => i,
+      None => return List { head: self.head.clone() },
+    };
+
+    let suffix = self.tails().nth(last_removed + 1).unwrap_or_default();
+    kept.into_iter()
+      .rev()
+      .filter(|&(i, _)| i < last_removed)
+      .fold(suffix, |list, (_, elem)| list.append(elem.clone()))
+  }
+
+  // stops at the first Err, otherwise the mapped list in the same order
+  pub fn try_map<U, E, F>(&self, f: F) -> Result<List<U>, E> where F: FnMut(&T) -> Result<U, E> {
+    self.iter().map(f).collect::<Result<Vec<_>, _>>().map(List::from)
+  }
+
+  pub fn partition<P>(&self, mut pred: P) -> (List<T>, List<T>) where T: Clone, P: FnMut(&T) -> bool {
+    let (matched, rest): (Vec<T>, Vec<T>) = self.iter().cloned().partition(|x| pred(x));
+    (List::from(matched), List::from(rest))
+  }
+
+  pub fn intersperse(&self, sep: T) -> List<T> where T: Clone {
+    let elems: Vec<&T> = self.iter().collect();
+    let mut list = List::new();
+    for (i, elem) in elems.into_iter().rev().enumerate() {
+      if i > 0 {
+        list = list.append(sep.clone());
+      }
+      list = list.append(elem.clone());
+    }
+    list
+  }
+
+  fn reversed(&self) -> List<T> where T: Clone {
+    self.iter().fold(List::new(), |list, elem| list.append(elem.clone()))
+  }
+
+  pub fn zipper(&self) -> Zipper<T> {
+    Zipper { prefix: List::new(), suffix: List { head: self.head.clone() } }
+  }
+
+  #[cfg(feature = "async")]
+  pub fn into_stream(self) -> IntoStream<T> where T: Clone {
+    IntoStream(self.into_iter())
+  }
+
+  pub fn to_vec(&self) -> Vec<T> where T: Clone {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<A: Clone, B: Clone> List<(A, B)> {
+  // builds both halves backwards as we go and flips them at the end, so no Vec in between
+  pub fn unzip(&self) -> (List<A>, List<B>) {
+    let (a, b) = self.iter().fold((List::new(), List::new()), |(a, b), (x, y)| {
+      (a.append(x.clone()), b.append(y.clone()))
+    });
+    (a.reversed(), b.reversed())
+  }
+}
+
+// append puts things at the front, so we have to build from the back of the vec to keep its order
+impl<T> From<Vec<T>> for List<T> {
+  fn from(vec: Vec<T>) -> Self {
+    vec.into_iter().rev().fold(List::new(), |list, elem| list.append(elem))
+  }
+}
+
+impl<T> FromIterator<T> for List<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    List::from(iter.into_iter().collect::<Vec<_>>())
+  }
+}
+
+impl<T> Default for List<T> {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl<T> Drop for List<T> {
@@ -64,11 +269,218 @@ impl<'a, T> Iterator for Iter<'a, T> {
   }
 }
 
-// Note that we can't implement IntoIter or IterMut for this type. We only have shared access to elements.
+impl<'a, T> Iter<'a, T> {
+  // batches of up to size elements, the last one can be short. panics on 0 like slice::chunks
+  pub fn chunks(self, size: usize) -> Chunks<'a, T> {
+    assert!(size != 0, "chunk size must be non-zero");
+    Chunks { iter: self, size }
+  }
+}
+
+pub struct Chunks<'a, T> {
+  iter: Iter<'a, T>,
+  size: usize,
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+  type Item = Vec<&'a T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let chunk: Vec<_> = self.iter.by_ref().take(self.size).collect();
+    if chunk.is_empty() { None } else { Some(chunk) }
+  }
+}
+
+// Everything is already in memory, so the stream is just IntoIter that's always ready
+#[cfg(feature = "async")]
+pub struct IntoStream<T>(IntoIter<T>);
+
+#[cfg(feature = "async")]
+impl<T: Clone> futures_core::Stream for IntoStream<T> {
+  type Item = T;
+
+  fn poll_next(mut self: std::pin::Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> std::task::Poll<Option<T>> {
+    std::task::Poll::Ready(self.0.next())
+  }
+}
+
+pub struct IterUntilShared<'a, T> {
+  next: Option<&'a Node<T>>,
+  stop: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for IterUntilShared<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let stop = self.stop;
+    self.next.take().filter(|&node| !stop.is_some_and(|stop| ptr::eq(node, stop))).map(|node| {
+      self.next = node.next.as_deref();
+      &node.elem
+    })
+  }
+}
+
+pub struct IterWithCounts<'a, T> {
+  next: Option<&'a Arc<Node<T>>>,
+}
+
+impl<'a, T> Iterator for IterWithCounts<'a, T> {
+  type Item = (&'a T, usize);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next.map(|node| {
+      self.next = node.next.as_ref();
+      (&node.elem, Arc::strong_count(node))
+    })
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharingStats {
+  pub nodes: usize,
+  pub shared_nodes: usize,
+  pub max_strong_count: usize,
+}
+
+pub struct Tails<T> {
+  next: Option<List<T>>,
+}
+
+impl<T> Iterator for Tails<T> {
+  type Item = List<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next.take().inspect(|list| {
+      if list.head.is_some() {
+        self.next = Some(list.tail());
+      }
+    })
+  }
+}
+
+// A zipper is a focus into the list: everything before the focus is kept reversed in prefix (nearest first),
+// and the focus is the head of suffix. Edits around the focus only touch the heads of those two lists, so they're
+// O(1) instead of rebuilding the whole spine. Moving clones one element across, since the nodes might be shared.
+//
+// [1, 2, 3, 4] with the focus on 3:
+// prefix: 2 -> 1
+// suffix: 3 -> 4
+//
+// The focus can also sit just past the end (suffix is empty), which is where insert appends.
+pub struct Zipper<T> {
+  prefix: List<T>,
+  suffix: List<T>,
+}
+
+impl<T: Clone> Zipper<T> {
+  pub fn focus(&self) -> Option<&T> {
+    self.suffix.head()
+  }
+
+  pub fn left(&mut self) -> bool {
+    match self.prefix.head() {
+      Some(elem) => {
+        self.suffix = self.suffix.append(elem.clone());
+        self.prefix = self.prefix.tail();
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn right(&mut self) -> bool {
+    match self.suffix.head() {
+      Some(elem) => {
+        self.prefix = self.prefix.append(elem.clone());
+        self.suffix = self.suffix.tail();
+        true
+      }
+      None => false,
+    }
+  }
+
+  // replace the focused element, false if we're past the end
+  pub fn set(&mut self, elem: T) -> bool {
+    if self.suffix.head().is_none() {
+      return false;
+    }
+    self.suffix = self.suffix.tail().append(elem);
+    true
+  }
+
+  // insert before the focus, the new element becomes the focus
+  pub fn insert(&mut self, elem: T) {
+    self.suffix = self.suffix.append(elem);
+  }
+
+  // remove the focused element, the one after it becomes the focus
+  pub fn delete(&mut self) -> bool {
+    if self.suffix.head().is_none() {
+      return false;
+    }
+    self.suffix = self.suffix.tail();
+    true
+  }
+
+  // zip the prefix back onto the suffix. only the prefix gets copied, the suffix is shared as is
+  pub fn rebuild(&self) -> List<T> {
+    self.prefix.iter().fold(List { head: self.suffix.head.clone() }, |list, elem| list.append(elem.clone()))
+  }
+}
+
+// once we're looking at the same node on both sides, everything after it is equal too
+fn spine_starts_with<T: PartialEq>(mut link: Option<&Arc<Node<T>>>, mut prefix: Option<&Arc<Node<T>>>) -> bool {
+  loop {
+    match (link, prefix) {
+      (_, None) => return true,
+      (None, Some(_)) => return false,
+      (Some(a), Some(b)) if Arc::ptr_eq(a, b) => return true,
+      (Some(a), Some(b)) if a.elem != b.elem => return false,
+      (Some(a), Some(b)) => {
+        link = a.next.as_ref();
+        prefix = b.next.as_ref();
+      }
+    }
+  }
+}
+
+// Note that we can't implement IterMut for this type. We only have shared access to elements.
+//
+// IntoIter is possible though if we're allowed to clone: whenever we're the only one holding a node we can move
+// the element out of it, and when someone else shares it we clone the element and leave the node to them.
+// Arc<Node<T>> is Send when T is Send + Sync, so the iterator can be handed off to another thread.
+pub struct IntoIter<T>(List<T>);
+
+impl<T: Clone> IntoIterator for List<T> {
+  type Item = T;
+  type IntoIter = IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter(self)
+  }
+}
+
+impl<T: Clone> Iterator for IntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.0.head.take().map(|node| match Arc::try_unwrap(node) {
+      Ok(mut node) => {
+        self.0.head = node.next.take();
+        node.elem
+      }
+      Err(node) => {
+        self.0.head = node.next.clone();
+        node.elem.clone()
+      }
+    })
+  }
+}
 
 #[cfg(test)]
 mod test {
-  use super::List;
+  use super::{List, SharingStats};
   #[test]
   fn basics() {
     let list = List::new();
@@ -100,4 +512,287 @@ mod test {
     assert_eq!(iter.next(), Some(&2));
     assert_eq!(iter.next(), Some(&1));
   }
+
+  #[test]
+  fn snoc() {
+    let list = List::new().snoc(1).snoc(2).snoc(3);
+    assert_eq!(list.to_vec(), vec![1, 2, 3]);
+
+    let longer = list.snoc(4);
+    assert_eq!(longer.to_vec(), vec![1, 2, 3, 4]);
+    // the original is untouched
+    assert_eq!(list.to_vec(), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn dedup() {
+    let list = List::from(vec![1, 1, 2, 3, 3, 3, 4, 5]);
+    let deduped = list.dedup();
+    assert_eq!(deduped.to_vec(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(list.to_vec(), vec![1, 1, 2, 3, 3, 3, 4, 5]);
+
+    // [4, 5] comes after the last duplicate, so it's shared rather than copied
+    let shared = deduped.tails().nth(3).unwrap();
+    assert!(std::ptr::eq(shared.head().unwrap(), list.iter().nth(6).unwrap()));
+
+    // nothing to remove means we get the same list back
+    let list = List::from(vec![1, 2, 3]);
+    assert!(std::ptr::eq(list.dedup().head().unwrap(), list.head().unwrap()));
+
+    let list = List::from(vec!["a", "A", "b", "B", "b", "c"]);
+    let deduped = list.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    assert_eq!(deduped.to_vec(), vec!["a", "b", "c"]);
+
+    assert_eq!(List::<i32>::new().dedup().head(), None);
+  }
+
+  #[test]
+  fn zipper() {
+    let list = List::from(vec![1, 2, 3, 4]);
+    let mut zipper = list.zipper();
+    assert_eq!(zipper.focus(), Some(&1));
+    assert!(!zipper.left());
+
+    assert!(zipper.right());
+    assert!(zipper.right());
+    assert_eq!(zipper.focus(), Some(&3));   // [1, 2, (3), 4]
+
+    assert!(zipper.set(30));                // [1, 2, (30), 4]
+    zipper.insert(25);                      // [1, 2, (25), 30, 4]
+    assert!(zipper.left());
+    assert!(zipper.delete());               // [1, (25), 30, 4]
+    assert_eq!(zipper.focus(), Some(&25));
+    assert_eq!(zipper.rebuild().to_vec(), vec![1, 25, 30, 4]);
+
+    while zipper.right() {}
+    assert_eq!(zipper.focus(), None);
+    assert!(!zipper.set(0));
+    assert!(!zipper.delete());
+    zipper.insert(5);                       // [1, 25, 30, 4, (5)]
+    assert_eq!(zipper.rebuild().to_vec(), vec![1, 25, 30, 4, 5]);
+
+    // the original is untouched
+    assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn iter_until_shared() {
+    let snapshot = List::from(vec![3, 4, 5]);
+    let current = snapshot.append(2).append(1);
+    let changes: Vec<_> = current.iter_until_shared(&snapshot).collect();
+    assert_eq!(changes, vec![&1, &2]);
+
+    // works from the other side too, and with branches off a common ancestor
+    assert_eq!(snapshot.iter_until_shared(&current).count(), 0);
+    let branch = snapshot.tail().append(9);
+    let changes: Vec<_> = current.iter_until_shared(&branch).collect();
+    assert_eq!(changes, vec![&1, &2, &3]);
+
+    // equal values in separate nodes don't count as shared
+    let lookalike = List::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(current.iter_until_shared(&lookalike).count(), 5);
+    assert_eq!(current.iter_until_shared(&List::new()).count(), 5);
+  }
+
+  #[test]
+  fn sharing() {
+    let base = List::from(vec![3, 4]);
+    let counts: Vec<_> = base.iter_with_counts().map(|(_, count)| count).collect();
+    assert_eq!(counts, vec![1, 1]);
+
+    let a = base.append(2);
+    let b = base.append(1);
+    let counts: Vec<_> = a.iter_with_counts().collect();
+    // 3 is held by base, a's 2 and b's 1
+    assert_eq!(counts, vec![(&2, 1), (&3, 3), (&4, 1)]);
+
+    assert_eq!(a.sharing_stats(), SharingStats { nodes: 3, shared_nodes: 1, max_strong_count: 3 });
+    drop(b);
+    assert_eq!(a.sharing_stats(), SharingStats { nodes: 3, shared_nodes: 1, max_strong_count: 2 });
+    drop(base);
+    assert_eq!(a.sharing_stats(), SharingStats { nodes: 3, shared_nodes: 0, max_strong_count: 1 });
+  }
+
+  #[test]
+  fn positional() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.get(0), Some(&1));
+    assert_eq!(list.get(2), Some(&3));
+    assert_eq!(list.get(3), None);
+    assert_eq!(list.nth(1), Some(&2));
+    assert_eq!(list.last(), Some(&3));
+
+    let empty = List::<i32>::new();
+    assert_eq!(empty.get(0), None);
+    assert_eq!(empty.last(), None);
+  }
+
+  #[test]
+  fn try_map() {
+    let list = List::from(vec!["1", "2", "3"]);
+    let parsed = list.try_map(|s| s.parse::<i32>());
+    assert_eq!(parsed.unwrap().to_vec(), vec![1, 2, 3]);
+
+    let mut seen = 0;
+    let list = List::from(vec!["1", "x", "3"]);
+    let parsed = list.try_map(|s| {
+      seen += 1;
+      s.parse::<i32>()
+    });
+    assert!(parsed.is_err());
+    assert_eq!(seen, 2);
+  }
+
+  #[test]
+  fn partition() {
+    let list = List::from(vec![1, 2, 3, 4, 5]);
+    let (evens, odds) = list.partition(|&x| x % 2 == 0);
+    assert_eq!(evens.to_vec(), vec![2, 4]);
+    assert_eq!(odds.to_vec(), vec![1, 3, 5]);
+
+    let (all, none) = list.partition(|_| true);
+    assert_eq!(all.to_vec(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(none.head(), None);
+  }
+
+  #[test]
+  fn unzip() {
+    let list = List::from(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    let (nums, chars) = list.unzip();
+    assert_eq!(nums.to_vec(), vec![1, 2, 3]);
+    assert_eq!(chars.to_vec(), vec!['a', 'b', 'c']);
+
+    let (nums, chars) = List::<(i32, char)>::new().unzip();
+    assert_eq!(nums.head(), None);
+    assert_eq!(chars.head(), None);
+  }
+
+  #[test]
+  fn intersperse() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.intersperse(0).to_vec(), vec![1, 0, 2, 0, 3]);
+
+    assert_eq!(List::from(vec![1]).intersperse(0).to_vec(), vec![1]);
+    assert_eq!(List::<i32>::new().intersperse(0).head(), None);
+  }
+
+  #[test]
+  fn search() {
+    let list = List::from(vec![1, 2, 3, 4]);
+
+    assert!(list.contains(&3));
+    assert!(!list.contains(&5));
+
+    assert_eq!(list.find(|&x| x % 2 == 0), Some(&2));
+    assert_eq!(list.find(|&x| x > 10), None);
+
+    assert_eq!(list.position(|&x| x == 4), Some(3));
+    assert_eq!(list.position(|&x| x == 0), None);
+
+    let empty = List::<i32>::new();
+    assert!(!empty.contains(&1));
+    assert_eq!(empty.position(|_| true), None);
+  }
+
+  #[test]
+  fn prefixes_and_suffixes() {
+    let list = List::from(vec![1, 2, 3, 4]);
+    assert!(list.starts_with(&List::from(vec![1, 2])));
+    assert!(list.starts_with(&list));
+    assert!(list.starts_with(&List::new()));
+    assert!(!list.starts_with(&List::from(vec![2])));
+    assert!(!list.starts_with(&List::from(vec![1, 2, 3, 4, 5])));
+
+    assert!(list.ends_with(&list.tail().tail()));
+    assert!(list.ends_with(&List::from(vec![3, 4])));
+    assert!(list.ends_with(&List::new()));
+    assert!(!list.ends_with(&List::from(vec![3])));
+    assert!(!list.ends_with(&List::from(vec![0, 1, 2, 3, 4])));
+
+    let empty = List::<i32>::new();
+    assert!(empty.starts_with(&List::new()));
+    assert!(!empty.ends_with(&List::from(vec![1])));
+  }
+
+  #[test]
+  fn tails() {
+    let list = List::from(vec![1, 2, 3]);
+
+    let tails: Vec<_> = list.tails().map(|tail| tail.to_vec()).collect();
+    assert_eq!(tails, vec![vec![1, 2, 3], vec![2, 3], vec![3], vec![]]);
+
+    // the suffixes share the original nodes
+    let second = list.tails().nth(1).unwrap();
+    assert!(std::ptr::eq(second.head().unwrap(), list.iter().nth(1).unwrap()));
+
+    assert_eq!(List::<i32>::new().tails().count(), 1);
+  }
+
+  #[test]
+  fn chunks() {
+    let list = List::from(vec![1, 2, 3, 4, 5]);
+    let chunks: Vec<_> = list.iter().chunks(2).collect();
+    assert_eq!(chunks, vec![vec![&1, &2], vec![&3, &4], vec![&5]]);
+
+    assert_eq!(list.iter().chunks(5).count(), 1);
+    assert_eq!(List::<i32>::new().iter().chunks(3).next(), None);
+  }
+
+  #[test]
+  #[should_panic]
+  fn chunks_zero() {
+    List::from(vec![1]).iter().chunks(0);
+  }
+
+  #[test]
+  fn into_iter() {
+    let list = List::from(vec![1, 2, 3]);
+    let shared = list.tail();
+
+    let elems: Vec<_> = list.into_iter().collect();
+    assert_eq!(elems, vec![1, 2, 3]);
+    // the nodes we shared are still there
+    assert_eq!(shared.to_vec(), vec![2, 3]);
+  }
+
+  #[test]
+  fn into_iter_send() {
+    fn assert_send<I: Send>(_: &I) {}
+
+    let list = List::from(vec![String::from("a"), String::from("b")]);
+    let iter = list.tail().into_iter();
+    assert_send(&iter);
+
+    let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+    assert_eq!(worker.join().unwrap(), vec![String::from("b")]);
+    assert_eq!(list.to_vec(), vec![String::from("a"), String::from("b")]);
+  }
+
+  #[cfg(feature = "async")]
+  #[test]
+  fn into_stream() {
+    use futures_core::Stream;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    let mut stream = List::from(vec![1, 2]).into_stream();
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+  }
+
+  #[test]
+  fn vec_conversions() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.head(), Some(&1));
+    assert_eq!(list.to_vec(), vec![1, 2, 3]);
+
+    let list: List<_> = (1..=3).map(|x| x * 10).collect();
+    assert_eq!(list.to_vec(), vec![10, 20, 30]);
+
+    let list = List::<i32>::from(Vec::new());
+    assert_eq!(list.head(), None);
+    assert!(list.to_vec().is_empty());
+  }
 }